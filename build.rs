@@ -14,7 +14,7 @@ mod generate {
         fmt::Write,
         fs::File,
         io::{BufRead, BufReader},
-        path::PathBuf,
+        path::{Path, PathBuf},
     };
 
     /// Generate bindings for _surena_ and/or _mirabel_.
@@ -50,6 +50,8 @@ mod generate {
             ]);
         }
 
+        let headers_source = find_headers();
+
         let mut builder = bindgen::Builder::default()
             .parse_callbacks(Box::new(bindgen::CargoCallbacks))
             .derive_default(true)
@@ -60,15 +62,32 @@ mod generate {
             writeln!(contents, r#"#include "{header}""#).unwrap();
         }
         builder = builder.header_contents("wrapper.h", &contents);
-        for allow in finalize_mirabel_headers(allowed_project) {
-            // prevent compilation errors from compiling libc headers
-            builder = builder.allowlist_file(regex::escape(&allow));
+        match &headers_source {
+            Headers::Submodule => {
+                for allow in finalize_mirabel_headers(allowed_project) {
+                    // prevent compilation errors from compiling libc headers
+                    builder = builder.allowlist_file(path_regex(&allow));
+                }
+                for include in mirabel_includes() {
+                    let include: PathBuf = ["mirabel", &include].iter().collect();
+                    builder = builder.clang_arg(format!("-I{}", include.display()));
+                }
+            }
+            Headers::System(includes) => {
+                // Installed headers lose the `includes/` prefix of the source tree.
+                for allow in &allowed_project {
+                    let allow = allow.split_once("includes/").map_or(*allow, |(_, h)| h);
+                    builder = builder
+                        .allowlist_file(format!(r#"(?:^|.*{SEPARATOR}){}"#, path_regex(allow)));
+                }
+                for include in includes {
+                    builder = builder.clang_arg(format!("-I{}", include.display()));
+                }
+            }
         }
         for allow in allowed_system {
-            builder = builder.allowlist_file(format!(r#"(?:^|.*/){}"#, regex::escape(allow)));
-        }
-        for include in mirabel_includes() {
-            builder = builder.clang_arg(format!("-Imirabel/{include}"));
+            builder =
+                builder.allowlist_file(format!(r#"(?:^|.*{SEPARATOR}){}"#, path_regex(allow)));
         }
         // Block variables which break because of https://github.com/rust-lang/rust-bindgen/issues/753
         let builder = builder.blocklist_item(regex::escape("LS_ERR"));
@@ -81,6 +100,22 @@ mod generate {
             .expect("failed to write bindings");
     }
 
+    /// Matches both path separators since clang may mix them on Windows.
+    const SEPARATOR: &str = r#"[/\\]"#;
+
+    /// Regex matching the `/`-separated `path` on the target platform.
+    ///
+    /// Paths are matched case-insensitively on Windows.
+    fn path_regex(path: &str) -> String {
+        let components: Vec<_> = path.split('/').map(regex::escape).collect();
+        let regex = components.join(SEPARATOR);
+        if env::var_os("CARGO_CFG_WINDOWS").is_some() {
+            format!("(?i){regex}")
+        } else {
+            regex
+        }
+    }
+
     /// Deduplicate headers and prepend `mirabel/`.
     fn finalize_mirabel_headers(mut headers: Vec<&str>) -> impl Iterator<Item = String> + '_ {
         headers.sort();
@@ -88,10 +123,115 @@ mod generate {
         headers.into_iter().map(|h| format!("mirabel/{h}"))
     }
 
+    const CMAKE_LISTS: &str = "mirabel/CMakeLists.txt";
+
+    /// Environment variable with the exact version required of installed
+    /// packages.
+    const PKG_VERSION_VAR: &str = "MIRABEL_SYS_PKG_VERSION";
+
+    /// Origin of the _mirabel_ and _surena_ headers.
+    enum Headers {
+        /// The `mirabel` submodule.
+        Submodule,
+        /// Installed packages with these include directories.
+        System(Vec<PathBuf>),
+    }
+
+    /// Use the submodule if checked out, otherwise look for installed
+    /// packages via pkg-config and then via CMake package configs.
+    fn find_headers() -> Headers {
+        println!("cargo:rerun-if-env-changed={PKG_VERSION_VAR}");
+        println!("cargo:rerun-if-env-changed=CMAKE_PREFIX_PATH");
+        if Path::new(CMAKE_LISTS).exists() {
+            return Headers::Submodule;
+        }
+
+        let package = if cfg!(feature = "mirabel") {
+            "mirabel"
+        } else {
+            "surena"
+        };
+        let (includes, version) = pkg_config_package(package)
+            .or_else(|| cmake_package(package))
+            .unwrap_or_else(|| {
+                panic!(
+                    "mirabel submodule not checked out and no {package} package found via \
+                     pkg-config or CMAKE_PREFIX_PATH"
+                )
+            });
+
+        if let Ok(required) = env::var(PKG_VERSION_VAR) {
+            match version {
+                Some(version) if version == required => {}
+                version => panic!(
+                    "{package} package has version {} but {PKG_VERSION_VAR} requires {required}",
+                    version.as_deref().unwrap_or("unknown")
+                ),
+            }
+        }
+        // Only existing paths may be tracked, otherwise cargo reruns every build.
+        for include in includes.iter().filter(|include| include.exists()) {
+            println!("cargo:rerun-if-changed={}", include.display());
+        }
+        Headers::System(includes)
+    }
+
+    /// Include directories and version of `package` from pkg-config.
+    fn pkg_config_package(package: &str) -> Option<(Vec<PathBuf>, Option<String>)> {
+        // Only the headers are needed because the host links the plugins.
+        let library = pkg_config::Config::new()
+            .cargo_metadata(false)
+            .env_metadata(true)
+            .probe(package)
+            .ok()?;
+        Some((library.include_paths, Some(library.version)))
+    }
+
+    /// Include directory and version of `package` from an installed CMake
+    /// package config.
+    fn cmake_package(package: &str) -> Option<(Vec<PathBuf>, Option<String>)> {
+        let mut prefixes: Vec<PathBuf> = env::var_os("CMAKE_PREFIX_PATH")
+            .map(|paths| env::split_paths(&paths).collect())
+            .unwrap_or_default();
+        if env::var_os("CARGO_CFG_UNIX").is_some() {
+            prefixes.extend(["/usr/local", "/usr"].map(PathBuf::from));
+        }
+
+        for prefix in prefixes {
+            for dir in ["lib/cmake", "lib64/cmake", "share"] {
+                let dir = prefix.join(dir).join(package);
+                let config = [format!("{package}Config"), format!("{package}-config")]
+                    .into_iter()
+                    .find(|name| dir.join(format!("{name}.cmake")).is_file());
+                let Some(config) = config else {
+                    continue;
+                };
+
+                let version_file = if config.ends_with("Config") {
+                    format!("{config}Version.cmake")
+                } else {
+                    format!("{config}-version.cmake")
+                };
+                let version = cmake_package_version(&dir.join(version_file));
+                return Some((vec![prefix.join("include")], version));
+            }
+        }
+        None
+    }
+
+    /// Parse `set(PACKAGE_VERSION "...")` from a CMake version file.
+    fn cmake_package_version(path: &Path) -> Option<String> {
+        let file = File::open(path).ok()?;
+        BufReader::new(file).lines().find_map(|line| {
+            let line = line.ok()?;
+            let rest = line.trim().strip_prefix("set(PACKAGE_VERSION")?;
+            let version = rest.trim().strip_suffix(')')?.trim().trim_matches('"');
+            Some(version.to_string())
+        })
+    }
+
     /// Returns a list of the includes in the _mirabel_ `CMakeLists.txt`.
     fn mirabel_includes() -> Vec<String> {
-        const CMAKE_LISTS: &str = "mirabel/CMakeLists.txt";
-
         println!("cargo:rerun-if-changed={CMAKE_LISTS}");
         let file = File::open(CMAKE_LISTS).expect("failed to open mirabel's CMakeLists.txt");
         let mut reader = BufReader::new(file);
@@ -0,0 +1,258 @@
+//! Wrapper for the `(void* begin, void* end)` buffer pairs of the C APIs.
+//!
+//! These are used, e.g., by serialized [`game_init`](crate::sys::game_init)s
+//! and the `serialize` game method.
+
+use std::{
+    ffi::c_void,
+    fmt::Debug,
+    mem::{self, ManuallyDrop},
+    ops::Deref,
+    ptr::{null, NonNull},
+    slice::from_raw_parts,
+};
+
+/// Function used to free host-allocated buffers.
+pub type FreeFn = unsafe extern "C" fn(*mut c_void);
+
+/// Byte buffer which is borrowed, owned by Rust, or allocated by the host.
+///
+/// Host-allocated buffers are freed with the supplied [`FreeFn`] on drop.
+/// Clones of borrowed buffers stay borrowed, all other clones are owned.
+/// Blobs compare by their contents.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::blob::Blob;
+/// let data = [1u8, 2, 3];
+/// let (begin, end) = Blob::borrowed(&data).as_raw();
+/// let blob = unsafe { Blob::from_raw(begin, end) }.unwrap();
+/// assert_eq!(&[1, 2, 3], blob.as_slice());
+/// assert_eq!(vec![1, 2, 3], blob.to_vec());
+/// ```
+pub struct Blob<'l>(Repr<'l>);
+
+enum Repr<'l> {
+    Borrowed(&'l [u8]),
+    Owned(Vec<u8>),
+    Host {
+        buf: NonNull<u8>,
+        len: usize,
+        free: FreeFn,
+    },
+}
+
+impl<'l> Blob<'l> {
+    #[inline]
+    pub fn borrowed(buf: &'l [u8]) -> Self {
+        Self(Repr::Borrowed(buf))
+    }
+
+    #[inline]
+    pub fn owned(buf: Vec<u8>) -> Self {
+        Self(Repr::Owned(buf))
+    }
+
+    /// Borrow the buffer between `begin` and `end`.
+    ///
+    /// Two NULL pointers result in an empty buffer.
+    /// Returns [`None`] if only one pointer is NULL or `end` is before
+    /// `begin`.
+    ///
+    /// # Safety
+    /// Unless NULL, `begin` and `end` must delimit a single valid allocation
+    /// which outlives `'l`.
+    #[inline]
+    pub unsafe fn from_raw(begin: *const c_void, end: *const c_void) -> Option<Self> {
+        slice_from_raw(begin, end).map(Self::borrowed)
+    }
+
+    /// Take ownership of a buffer allocated by the host.
+    ///
+    /// `free` is called with `begin` when the [`Blob`] is dropped, unless
+    /// `begin` is NULL.
+    /// The blob may be dropped on any thread.
+    /// For invalid pointer pairs, see [`Blob::from_raw`], [`None`] is
+    /// returned and the buffer is not taken.
+    ///
+    /// # Safety
+    /// See [`Blob::from_raw`].
+    /// Additionally, `free` must be the matching deallocation function, must
+    /// be callable from any thread and the buffer must not be accessed
+    /// elsewhere.
+    pub unsafe fn from_host(begin: *mut c_void, end: *mut c_void, free: FreeFn) -> Option<Self> {
+        let len = slice_from_raw(begin, end)?.len();
+        Some(match NonNull::new(begin.cast()) {
+            Some(buf) => Self(Repr::Host { buf, len, free }),
+            None => Self::borrowed(&[]),
+        })
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        match &self.0 {
+            Repr::Borrowed(buf) => buf,
+            Repr::Owned(buf) => buf,
+            Repr::Host { buf, len, .. } => unsafe { from_raw_parts(buf.as_ptr(), *len) },
+        }
+    }
+
+    #[inline]
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.as_slice().to_vec()
+    }
+
+    /// Copy a borrowed or host-allocated buffer so that it no longer depends
+    /// on `'l` or the host.
+    pub fn into_owned(self) -> Blob<'static> {
+        Blob::owned(self.into_vec())
+    }
+
+    /// Convert into an owned [`Vec`], copying the buffer if needed.
+    pub fn into_vec(self) -> Vec<u8> {
+        match self.into_repr() {
+            Repr::Owned(buf) => buf,
+            repr => Self(repr).to_vec(),
+        }
+    }
+
+    /// `(begin, end)` pointers for passing the buffer to the C API.
+    ///
+    /// They are only valid as long as `self` is neither dropped nor modified.
+    pub fn as_raw(&self) -> (*const c_void, *const c_void) {
+        let buf = self.as_slice();
+        if buf.is_empty() {
+            (null(), null())
+        } else {
+            let range = buf.as_ptr_range();
+            (range.start.cast(), range.end.cast())
+        }
+    }
+
+    /// Move out the representation without running [`Drop`].
+    fn into_repr(self) -> Repr<'l> {
+        let mut this = ManuallyDrop::new(self);
+        mem::replace(&mut this.0, Repr::Borrowed(&[]))
+    }
+}
+
+impl<'l> Drop for Blob<'l> {
+    fn drop(&mut self) {
+        if let Repr::Host { buf, free, .. } = self.0 {
+            unsafe { free(buf.as_ptr().cast()) };
+        }
+    }
+}
+
+// The host buffer is only read and `free` may be called from any thread.
+unsafe impl<'l> Send for Blob<'l> {}
+unsafe impl<'l> Sync for Blob<'l> {}
+
+impl<'l> Clone for Blob<'l> {
+    fn clone(&self) -> Self {
+        match &self.0 {
+            Repr::Borrowed(buf) => Self::borrowed(buf),
+            _ => Self::owned(self.to_vec()),
+        }
+    }
+}
+
+impl<'l> PartialEq for Blob<'l> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<'l> Eq for Blob<'l> {}
+
+impl<'l> Default for Blob<'l> {
+    #[inline]
+    fn default() -> Self {
+        Self::borrowed(&[])
+    }
+}
+
+impl<'l> Deref for Blob<'l> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<'l> Debug for Blob<'l> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Blob").field(&self.as_slice()).finish()
+    }
+}
+
+impl<'l> From<&'l [u8]> for Blob<'l> {
+    #[inline]
+    fn from(buf: &'l [u8]) -> Self {
+        Self::borrowed(buf)
+    }
+}
+
+impl From<Vec<u8>> for Blob<'static> {
+    #[inline]
+    fn from(buf: Vec<u8>) -> Self {
+        Self::owned(buf)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'l> serde::Serialize for Blob<'l> {
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+/// Deserialized into an owned buffer.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Blob<'static> {
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Self::owned)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Blob<'a> {
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self::borrowed(u.arbitrary()?))
+    }
+}
+
+/// Create a slice from a `(begin, end)` buffer pair.
+///
+/// Two NULL pointers result in an empty slice.
+/// Returns [`None`] if only one pointer is NULL or `end` is before `begin`.
+///
+/// # Safety
+/// Unless NULL, `begin` and `end` must delimit a single valid allocation.
+/// Make sure that the returned lifetime does not outlive the buffer.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::blob::slice_from_raw;
+/// let data = [1u8, 2];
+/// let (begin, end) = (data.as_ptr_range().start.cast(), data.as_ptr_range().end.cast());
+/// assert_eq!(Some(&data[..]), unsafe { slice_from_raw(begin, end) });
+/// assert_eq!(None, unsafe { slice_from_raw(end, begin) });
+/// assert_eq!(None, unsafe { slice_from_raw(begin, std::ptr::null()) });
+/// ```
+#[doc(hidden)]
+pub unsafe fn slice_from_raw<'l>(begin: *const c_void, end: *const c_void) -> Option<&'l [u8]> {
+    match (begin.is_null(), end.is_null()) {
+        (true, true) => Some(&[]),
+        (false, false) => {
+            let len = (end as usize).checked_sub(begin as usize)?;
+            Some(from_raw_parts(begin.cast(), len))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,120 @@
+//! Wrapper around the [`frontend_display_data`] handed to frontends.
+
+use std::{marker::PhantomData, mem::ManuallyDrop, ptr::NonNull};
+
+use crate::{
+    event::EventAny,
+    player::PlayerId,
+    sys::{event_queue_push, frontend_display_data},
+};
+
+/// Borrowed display data of a frontend.
+///
+/// _mirabel_ updates the display data between calls, so every getter reads
+/// the current value.
+///
+/// # Example
+/// ```no_run
+/// # use mirabel_sys::{display_data::DisplayData, event::EventAny, player::PlayerId};
+/// fn update(dd: &mut DisplayData, last_tick: &mut u64) {
+///     let delta = dd.ms_since(*last_tick);
+///     *last_tick = dd.ms_tick();
+///     if delta > 1000 && dd.view() == PlayerId::new(1) {
+///         dd.push_event(EventAny::new_game_move(PlayerId::new(1), 12));
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct DisplayData<'l> {
+    data: NonNull<frontend_display_data>,
+    phantom: PhantomData<&'l mut frontend_display_data>,
+}
+
+impl<'l> DisplayData<'l> {
+    /// Create a new [`DisplayData`] or [`None`] if `data` is NULL.
+    ///
+    /// # Safety
+    /// `data` must be valid for `'l` and its `outbound_queue` must be a valid
+    /// event queue.
+    #[inline]
+    pub unsafe fn new(data: *mut frontend_display_data) -> Option<Self> {
+        NonNull::new(data).map(|data| Self {
+            data,
+            phantom: Default::default(),
+        })
+    }
+
+    /// Milliseconds since an arbitrary point in time, e.g., for animations.
+    #[inline]
+    pub fn ms_tick(&self) -> u64 {
+        self.get().ms_tick
+    }
+
+    /// Milliseconds passed since the `earlier` [`DisplayData::ms_tick`].
+    #[inline]
+    pub fn ms_since(&self, earlier: u64) -> u64 {
+        self.ms_tick().saturating_sub(earlier)
+    }
+
+    /// Size of the framebuffer as `(width, height)`.
+    #[inline]
+    pub fn framebuffer_size(&self) -> (f32, f32) {
+        let data = self.get();
+        (data.fbw, data.fbh)
+    }
+
+    /// Area of the framebuffer available to the frontend.
+    pub fn viewport(&self) -> Viewport {
+        let data = self.get();
+        Viewport {
+            x: data.x,
+            y: data.y,
+            width: data.w,
+            height: data.h,
+        }
+    }
+
+    /// Player from whose perspective the game is displayed.
+    ///
+    /// This is [`PlayerId::NONE`] for spectators.
+    #[inline]
+    pub fn view(&self) -> PlayerId {
+        self.get().view.into()
+    }
+
+    /// Send `event` to _mirabel_ through the outbound queue.
+    pub fn push_event(&mut self, event: EventAny) {
+        let mut event = ManuallyDrop::new(event);
+        // The queue takes over the event, so it must not be destroyed here.
+        unsafe { event_queue_push(self.get().outbound_queue, &mut **event) };
+    }
+
+    #[inline]
+    fn get(&self) -> &frontend_display_data {
+        unsafe { self.data.as_ref() }
+    }
+}
+
+/// Rectangle in framebuffer coordinates.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Viewport {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Viewport {
+    /// Returns `true` if the point `(x, y)` lies within the viewport.
+    ///
+    /// # Example
+    /// ```
+    /// # use mirabel_sys::display_data::Viewport;
+    /// let viewport = Viewport { x: 10.0, y: 0.0, width: 100.0, height: 50.0 };
+    /// assert!(viewport.contains(10.0, 49.0));
+    /// assert!(!viewport.contains(5.0, 20.0));
+    /// ```
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y)
+    }
+}
@@ -1,11 +1,18 @@
 //! Helpers for error handling in plugin APIs.
 
-use std::{ffi::c_char, fmt::Display, num::NonZeroU32, ptr::null};
+use std::{
+    cell::RefCell,
+    ffi::c_char,
+    fmt::Display,
+    num::NonZeroU32,
+    ptr::null,
+    sync::{Mutex, PoisonError},
+};
 
 use crate::{
     cstr, cstr_to_rust,
     sys::{self, error_code, ERR_ERR_ENUM_DEFAULT_OFFSET},
-    ValidCStr, ValidCString,
+    CowCStr, ValidCStr, ValidCString,
 };
 
 /// Type for C-compatible error strings.
@@ -37,6 +44,16 @@ impl From<&ErrorString> for *const c_char {
     }
 }
 
+impl From<CowCStr<'static>> for ErrorString {
+    #[inline]
+    fn from(s: CowCStr<'static>) -> Self {
+        match s {
+            CowCStr::Borrowed(s) => ErrorString::Static(s),
+            CowCStr::Owned(s) => ErrorString::Dynamic(s),
+        }
+    }
+}
+
 /// Error type for API functions.
 ///
 /// The APIs always expect an error code and optionally an error message.
@@ -83,6 +100,14 @@ impl Error {
     }
 }
 
+/// Short explanation of `error` for end users.
+///
+/// See [`ErrorCode::user_message`].
+#[inline]
+pub fn user_message(error: &Error) -> String {
+    error.code.user_message().to_string()
+}
+
 impl From<ErrorCode> for Error {
     /// Create an error without a `message`.
     #[inline]
@@ -96,6 +121,7 @@ impl From<ErrorCode> for Error {
 
 /// _surena_ error codes as a Rust enum.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum ErrorCode {
     StateUnrecoverable,
@@ -167,6 +193,13 @@ impl From<ErrorCode> for error_code {
 
 impl Display for ErrorCode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(name) = match self {
+            ErrorCode::Custom(code) => code.name(),
+            _ => None,
+        } {
+            return write!(f, "{}", name);
+        }
+
         let code = error_code::from(*self);
         let error = unsafe { cstr_to_rust(sys::get_general_error_string(code)) };
         match error {
@@ -176,6 +209,38 @@ impl Display for ErrorCode {
     }
 }
 
+impl ErrorCode {
+    /// Short explanation of the error for end users.
+    ///
+    /// Unlike the [`Display`] output and error messages, this hides
+    /// implementation details and is suitable for status displays.
+    ///
+    /// # Example
+    /// ```
+    /// # use mirabel_sys::error::*;
+    /// assert_eq!("That move is not legal right now.", ErrorCode::InvalidMove.user_message());
+    /// ```
+    pub fn user_message(&self) -> &'static str {
+        match self {
+            ErrorCode::StateUnrecoverable | ErrorCode::StateCorrupted => {
+                "The game ran into an internal problem and cannot continue."
+            }
+            ErrorCode::OutOfMemory => "The game ran out of memory.",
+            ErrorCode::FeatureUnsupported => "This game does not support that.",
+            ErrorCode::MissingHiddenState => "That needs information which is hidden from you.",
+            ErrorCode::InvalidInput => "That input was not understood.",
+            ErrorCode::InvalidPlayer => "It is not that player's turn.",
+            ErrorCode::InvalidMove => "That move is not legal right now.",
+            ErrorCode::InvalidOptions => "The game options are not valid.",
+            ErrorCode::InvalidLegacy => "The carried over match data is not valid.",
+            ErrorCode::InvalidState => "That position is not valid.",
+            ErrorCode::UnstablePosition => "The position is still settling, please wait.",
+            ErrorCode::Retry => "Please try again.",
+            ErrorCode::CustomAny | ErrorCode::Custom(_) => "The game reported a problem.",
+        }
+    }
+}
+
 /// Wrapper for custom error codes.
 ///
 /// It assures that the error code is not smaller than [`ERR_ERR_ENUM_DEFAULT_OFFSET`].
@@ -200,6 +265,38 @@ impl CustomCode {
     pub fn get(&self) -> error_code {
         self.0.get()
     }
+
+    /// Name registered with [`register_custom`], if any.
+    pub fn name(&self) -> Option<&'static str> {
+        let registry = CUSTOM_NAMES.lock().unwrap_or_else(PoisonError::into_inner);
+        registry
+            .iter()
+            .find(|(code, _)| code == self)
+            .map(|(_, name)| *name)
+    }
+}
+
+static CUSTOM_NAMES: Mutex<Vec<(CustomCode, &'static str)>> = Mutex::new(Vec::new());
+
+/// Register a readable `name` for a custom error `code`.
+///
+/// [`ErrorCode`]'s [`Display`] implementation prints this name instead of
+/// `custom[N]`.
+/// Registering a code again replaces its name.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{error::*, sys::ERR_ERR_ENUM_DEFAULT_OFFSET};
+/// let code = CustomCode::new(ERR_ERR_ENUM_DEFAULT_OFFSET + 1).unwrap();
+/// register_custom(code, "engine timeout");
+/// assert_eq!(Some("engine timeout"), code.name());
+/// ```
+pub fn register_custom(code: CustomCode, name: &'static str) {
+    let mut registry = CUSTOM_NAMES.lock().unwrap_or_else(PoisonError::into_inner);
+    match registry.iter_mut().find(|(c, _)| *c == code) {
+        Some((_, old)) => *old = name,
+        None => registry.push((code, name)),
+    }
 }
 
 impl From<CustomCode> for error_code {
@@ -209,9 +306,72 @@ impl From<CustomCode> for error_code {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for CustomCode {
+    #[inline]
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        self.get().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CustomCode {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let code = error_code::deserialize(deserializer)?;
+        Self::new(code).ok_or_else(|| serde::de::Error::custom("error code is not custom"))
+    }
+}
+
 /// Result type using the special [`Error`] type.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Conversion of [`Option`]s into [`Result`]s with a specific [`ErrorCode`].
+pub trait OptionExt<T> {
+    /// Map [`None`] to an [`Error`] with `code`.
+    ///
+    /// # Example
+    /// ```
+    /// # use mirabel_sys::error::*;
+    /// let width: Option<u8> = None;
+    /// assert_eq!(ErrorCode::InvalidOptions, width.ok_or_code(ErrorCode::InvalidOptions).unwrap_err().code);
+    /// ```
+    fn ok_or_code(self, code: ErrorCode) -> Result<T>;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    #[inline]
+    fn ok_or_code(self, code: ErrorCode) -> Result<T> {
+        self.ok_or_else(|| code.into())
+    }
+}
+
+/// Conversion of foreign errors into [`Error`]s with a specific [`ErrorCode`].
+pub trait ResultExt<T> {
+    /// Map the error to an [`Error`] with `code`.
+    ///
+    /// The [`Display`] output of the foreign error becomes the message.
+    /// NUL bytes are removed from it.
+    ///
+    /// # Example
+    /// ```
+    /// # use mirabel_sys::error::*;
+    /// let error = "x".parse::<u8>().map_code(ErrorCode::InvalidState).unwrap_err();
+    /// assert_eq!(ErrorCode::InvalidState, error.code);
+    /// ```
+    fn map_code(self, code: ErrorCode) -> Result<T>;
+}
+
+impl<T, E: Display> ResultExt<T> for std::result::Result<T, E> {
+    fn map_code(self, code: ErrorCode) -> Result<T> {
+        self.map_err(|e| Error::new_dynamic(code, e.to_string().replace('\0', "")))
+    }
+}
+
 /// Transform an [`error_code`] to a [`Result`](std::result::Result) with
 /// [`ErrorCode`].
 ///
@@ -234,3 +394,50 @@ pub fn code_to_result(code: error_code) -> std::result::Result<(), ErrorCode> {
         None => Ok(()),
     }
 }
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<Error>> = const { RefCell::new(None) };
+}
+
+/// Stage `error` as the last error of this thread and return its code.
+///
+/// This is meant for API functions which can only return an
+/// [`error_code`].
+/// The message can later be retrieved via [`last_error_message`] or
+/// [`take_last_error`].
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{cstr_to_rust, error::*, sys::ERR_ERR_INVALID_MOVE};
+/// let code = stage_error(Error::new_static(ErrorCode::InvalidMove, "occupied\0"));
+/// assert_eq!(ERR_ERR_INVALID_MOVE, code);
+/// assert_eq!(Some("occupied"), unsafe { cstr_to_rust(last_error_message()) });
+///
+/// let error = take_last_error().unwrap();
+/// assert_eq!(ErrorCode::InvalidMove, error.code);
+/// assert!(take_last_error().is_none());
+/// ```
+pub fn stage_error(error: Error) -> error_code {
+    let code = error.code.into();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(error));
+    code
+}
+
+/// Message of the error staged by [`stage_error`] on this thread.
+///
+/// Suitable as return value of a `get_last_error` function.
+/// Returns NULL if no error or no message is staged.
+/// The pointer stays valid until the next error is staged or taken on this
+/// thread.
+pub fn last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| match &*last.borrow() {
+        Some(error) => (&error.message).into(),
+        None => null(),
+    })
+}
+
+/// Remove and return the error staged by [`stage_error`] on this thread.
+#[inline]
+pub fn take_last_error() -> Option<Error> {
+    LAST_ERROR.with(|last| last.borrow_mut().take())
+}
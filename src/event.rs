@@ -1,13 +1,23 @@
 //! Wrapper for the _mirabel_ event framework.
 
-pub use crate::{game_init::GameInit, sys::game_methods, sys::move_code, sys::player_id};
+pub use crate::{
+    game::GameMethodsRef,
+    game_init::{GameInit, OwnedGameInit},
+    player::PlayerId,
+    sys::game_methods,
+    sys::move_code,
+    sys::player_id,
+};
 
 use std::{
+    borrow::Cow,
+    ffi::CStr,
     mem::MaybeUninit,
     ops::{Deref, DerefMut},
+    ptr::null,
 };
 
-use crate::{sys::*, ValidCStr};
+use crate::{sys::*, ValidCStr, ValidCString};
 
 /// Wrapper for an owned [`event_any`].
 ///
@@ -30,19 +40,93 @@ impl EventAny {
         unsafe { self.base.type_ }
     }
 
-    pub fn to_rust(&self) -> EventEnum {
+    pub fn to_rust(&self) -> EventEnum<'_> {
         unsafe { EventEnum::new(self) }
     }
 
-    pub fn new_game_move(player: player_id, code: move_code) -> Self {
+    pub fn new_game_move(player: PlayerId, code: move_code) -> Self {
         let mut event = MaybeUninit::<event_any>::uninit();
         unsafe {
             // This sets the sync_counter to 0 because it is ignored in events
             // originating from plugins anyway.
-            event_create_game_move(event.as_mut_ptr(), 0, player, code);
+            event_create_game_move(event.as_mut_ptr(), 0, player.into(), code);
         }
         unsafe { Self(event.assume_init()) }
     }
+
+    pub fn new_game_state(state: Option<ValidCStr>) -> Self {
+        let mut event = MaybeUninit::<event_any>::uninit();
+        unsafe {
+            event_create_game_state(event.as_mut_ptr(), 0, state.map_or(null(), Into::into));
+        }
+        unsafe { Self(event.assume_init()) }
+    }
+
+    pub fn new_game_unload() -> Self {
+        let mut event = MaybeUninit::<event_any>::uninit();
+        unsafe {
+            event_create_type(event.as_mut_ptr(), EVENT_TYPE_E_EVENT_TYPE_GAME_UNLOAD);
+        }
+        unsafe { Self(event.assume_init()) }
+    }
+
+    /// Create a new chat message event.
+    ///
+    /// Control characters are removed from `text`.
+    /// Message ID, author and timestamp are left to the host.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use mirabel_sys::event::*;
+    /// let event = EventAny::new_chat_msg("gg\n");
+    /// let EventEnum::ChatMsg(chat) = event.to_rust() else { unreachable!() };
+    /// assert_eq!("gg", chat.message);
+    /// ```
+    #[inline]
+    pub fn new_chat_msg(text: &str) -> Self {
+        Self::chat_msg(0, ClientId(0), 0, text)
+    }
+
+    fn chat_msg(msg_id: u32, author: ClientId, timestamp: u64, text: &str) -> Self {
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        // NUL is a control character and has been removed.
+        let text = ValidCString::try_from(text).unwrap();
+        let mut event = MaybeUninit::<event_any>::uninit();
+        unsafe {
+            event_create_chat_msg(
+                event.as_mut_ptr(),
+                msg_id,
+                author.get(),
+                timestamp,
+                text.as_ptr(),
+            );
+        }
+        unsafe { Self(event.assume_init()) }
+    }
+
+    /// Create a new game load event.
+    ///
+    /// Returns [`None`] if a string in `init_info` contains a NUL byte.
+    ///
+    /// # Safety
+    /// `methods` must point to valid [`game_methods`] which outlive the event.
+    pub unsafe fn new_game_load_methods(
+        methods: *const game_methods,
+        init_info: GameInit,
+    ) -> Option<Self> {
+        let init_info = init_info.into_sys().ok()?;
+        let mut event = MaybeUninit::<event_any>::uninit();
+        // The event copies the referenced data of init_info.
+        event_create_game_load_methods(event.as_mut_ptr(), methods, *init_info);
+        Some(Self(event.assume_init()))
+    }
+
+    /// Overwrite the client and lobby ID with the ones from `base`.
+    fn with_ids(mut self, base: &Event) -> Self {
+        self.0.base.client_id = base.client_id.get();
+        self.0.base.lobby_id = base.lobby_id.get();
+        self
+    }
 }
 
 impl Deref for EventAny {
@@ -74,6 +158,7 @@ pub enum EventEnum<'l> {
     GameUnload(Event),
     GameState(EventGameState<'l>),
     GameMove(EventGameMove),
+    ChatMsg(EventChat<'l>),
     Unknown,
 }
 
@@ -85,7 +170,10 @@ impl<'l> EventEnum<'l> {
     unsafe fn new(event: &'l event_any) -> Self {
         match event.base.type_ {
             EVENT_TYPE_E_EVENT_TYPE_GAME_LOAD_METHODS => {
-                Self::GameLoadMethods(EventGameLoadMethods::new(&event.game_load_methods))
+                match EventGameLoadMethods::new(&event.game_load_methods) {
+                    Some(e) => Self::GameLoadMethods(e),
+                    None => Self::Unknown,
+                }
             }
             EVENT_TYPE_E_EVENT_TYPE_GAME_UNLOAD => Self::GameUnload(Event::new(&event.base)),
             EVENT_TYPE_E_EVENT_TYPE_GAME_STATE => {
@@ -94,38 +182,167 @@ impl<'l> EventEnum<'l> {
             EVENT_TYPE_E_EVENT_TYPE_GAME_MOVE => {
                 Self::GameMove(EventGameMove::new(&event.game_move))
             }
+            EVENT_TYPE_E_EVENT_TYPE_LOBBY_CHAT_MSG => {
+                Self::ChatMsg(EventChat::new(&event.chat_msg))
+            }
             _ => Self::Unknown,
         }
     }
+
+    /// Deep copy this event so that it no longer borrows the [`EventAny`].
+    pub fn to_owned(&self) -> OwnedEventEnum {
+        match self {
+            EventEnum::GameLoadMethods(e) => OwnedEventEnum::GameLoadMethods(e.to_owned()),
+            EventEnum::GameUnload(e) => OwnedEventEnum::GameUnload(*e),
+            EventEnum::GameState(e) => OwnedEventEnum::GameState(e.to_owned()),
+            EventEnum::GameMove(e) => OwnedEventEnum::GameMove(*e),
+            EventEnum::ChatMsg(e) => OwnedEventEnum::ChatMsg(OwnedEventChat {
+                base: e.base,
+                msg_id: e.msg_id,
+                author: e.author,
+                timestamp: e.timestamp,
+                message: e.message.to_string(),
+            }),
+            EventEnum::Unknown => OwnedEventEnum::Unknown,
+        }
+    }
+
+    /// The common part of the event or [`None`] for [`EventEnum::Unknown`].
+    pub fn base(&self) -> Option<&Event> {
+        match self {
+            EventEnum::GameLoadMethods(e) => Some(&e.base),
+            EventEnum::GameUnload(e) => Some(e),
+            EventEnum::GameState(e) => Some(&e.base),
+            EventEnum::GameMove(e) => Some(&e.base),
+            EventEnum::ChatMsg(e) => Some(&e.base),
+            EventEnum::Unknown => None,
+        }
+    }
 }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Event {
     pub type_: EVENT_TYPE,
-    pub client_id: u32,
-    pub lobby_id: u32,
+    pub client_id: ClientId,
+    pub lobby_id: LobbyId,
 }
 
 impl Event {
     unsafe fn new(event: &event) -> Self {
         Self {
             type_: event.type_,
-            client_id: event.client_id,
-            lobby_id: event.lobby_id,
+            client_id: ClientId(event.client_id),
+            lobby_id: LobbyId(event.lobby_id),
+        }
+    }
+}
+
+/// ID of a _mirabel_ client.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
+pub struct ClientId(u32);
+
+impl ClientId {
+    #[inline]
+    pub const fn new(id: u32) -> Self {
+        Self(id)
+    }
+
+    #[inline]
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+}
+
+/// ID of a _mirabel_ lobby.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
+pub struct LobbyId(u32);
+
+impl LobbyId {
+    #[inline]
+    pub const fn new(id: u32) -> Self {
+        Self(id)
+    }
+
+    #[inline]
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+}
+
+/// Tracks the current lobby and filters out events of other lobbies.
+///
+/// Hosts with multiple lobbies may still deliver events of a lobby which was
+/// already left.
+/// All events are accepted while no lobby is set.
+#[derive(Debug, Default, Clone)]
+pub struct SessionTracker {
+    lobby: Option<LobbyId>,
+}
+
+impl SessionTracker {
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    #[inline]
+    pub fn lobby(&self) -> Option<LobbyId> {
+        self.lobby
+    }
+
+    /// Switch to `lobby` or stop tracking with [`None`].
+    #[inline]
+    pub fn set_lobby(&mut self, lobby: Option<LobbyId>) {
+        self.lobby = lobby;
+    }
+
+    /// Returns `true` if `event` belongs to the current lobby.
+    #[inline]
+    pub fn is_current(&self, event: &Event) -> bool {
+        self.lobby.is_none() || self.lobby == Some(event.lobby_id)
+    }
+
+    /// Returns [`None`] for stale events of other lobbies.
+    ///
+    /// [`EventEnum::Unknown`] is always passed through.
+    pub fn filter<'l>(&self, event: EventEnum<'l>) -> Option<EventEnum<'l>> {
+        match event.base() {
+            Some(base) if !self.is_current(base) => None,
+            _ => Some(event),
         }
     }
 }
+/// Load event of a new game.
+///
+/// Events with NULL methods or invalid init info are converted to
+/// [`EventEnum::Unknown`].
 pub struct EventGameLoadMethods<'l> {
     pub base: Event,
-    // TODO: Provide safe wrapper for game_methods.
-    pub methods: *const game_methods,
+    pub methods: GameMethodsRef<'l>,
     pub init_info: GameInit<'l>,
 }
 
 impl<'l> EventGameLoadMethods<'l> {
-    unsafe fn new(event: &'l event_game_load_methods) -> Self {
-        Self {
+    unsafe fn new(event: &'l event_game_load_methods) -> Option<Self> {
+        Some(Self {
             base: Event::new(&event.base),
-            methods: event.methods,
-            init_info: GameInit::new(&event.init_info),
+            methods: GameMethodsRef::from_ptr(event.methods)?,
+            init_info: GameInit::new(&event.init_info).ok()?,
+        })
+    }
+
+    /// Deep copy this event so that it no longer borrows the [`EventAny`].
+    pub fn to_owned(&self) -> OwnedEventGameLoadMethods {
+        OwnedEventGameLoadMethods {
+            base: self.base,
+            methods: self.methods.as_ptr(),
+            init_info: (&self.init_info).into(),
         }
     }
 }
@@ -142,11 +359,21 @@ impl<'l> EventGameState<'l> {
             state: ValidCStr::new(event.state),
         }
     }
+
+    /// Deep copy this event so that it no longer borrows the [`EventAny`].
+    pub fn to_owned(&self) -> OwnedEventGameState {
+        OwnedEventGameState {
+            base: self.base,
+            state: self.state.map(|s| s.to_string()),
+        }
+    }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EventGameMove {
     pub base: Event,
-    pub player: player_id,
+    pub player: PlayerId,
     pub code: move_code,
 }
 
@@ -154,8 +381,282 @@ impl EventGameMove {
     unsafe fn new(event: &event_game_move) -> Self {
         Self {
             base: Event::new(&event.base),
-            player: event.player,
+            player: event.player.into(),
             code: event.code,
         }
     }
 }
+
+/// Lobby chat message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventChat<'l> {
+    pub base: Event,
+    pub msg_id: u32,
+    pub author: ClientId,
+    /// Timestamp assigned by the host.
+    pub timestamp: u64,
+    /// The text with invalid UTF-8 replaced and control characters removed.
+    pub message: Cow<'l, str>,
+}
+
+impl<'l> EventChat<'l> {
+    unsafe fn new(event: &'l event_chat_msg) -> Self {
+        let text = if event.text.is_null() {
+            Cow::Borrowed("")
+        } else {
+            CStr::from_ptr(event.text).to_string_lossy()
+        };
+        let message = if text.contains(char::is_control) {
+            Cow::Owned(text.chars().filter(|c| !c.is_control()).collect())
+        } else {
+            text
+        };
+        Self {
+            base: Event::new(&event.base),
+            msg_id: event.msg_id,
+            author: ClientId(event.author_client_id),
+            timestamp: event.timestamp,
+            message,
+        }
+    }
+}
+
+/// Owned version of [`EventEnum`] for storing or deferring events.
+///
+/// # Example
+/// ```no_run
+/// # use mirabel_sys::event::*;
+/// let event = EventAny::new_game_move(PlayerId::new(1), 42);
+/// let owned = event.to_rust().to_owned();
+/// drop(event);
+/// let event = owned.to_event().unwrap();
+/// assert!(matches!(event.to_rust(), EventEnum::GameMove(EventGameMove { code: 42, .. })));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum OwnedEventEnum {
+    GameLoadMethods(OwnedEventGameLoadMethods),
+    GameUnload(Event),
+    GameState(OwnedEventGameState),
+    GameMove(EventGameMove),
+    ChatMsg(OwnedEventChat),
+    Unknown,
+}
+
+impl OwnedEventEnum {
+    /// Create a new [`EventAny`] from this event, e.g., for re-sending it.
+    ///
+    /// Client and lobby IDs are preserved.
+    /// Returns [`None`] for [`OwnedEventEnum::Unknown`], NULL methods or if
+    /// a string contains a NUL byte.
+    pub fn to_event(&self) -> Option<EventAny> {
+        let event = match self {
+            OwnedEventEnum::GameLoadMethods(e) if e.methods.is_null() => return None,
+            OwnedEventEnum::GameLoadMethods(e) => unsafe {
+                EventAny::new_game_load_methods(e.methods, e.init_info.as_game_init())?
+                    .with_ids(&e.base)
+            },
+            OwnedEventEnum::GameUnload(e) => EventAny::new_game_unload().with_ids(e),
+            OwnedEventEnum::GameState(e) => {
+                let state: Option<ValidCString> =
+                    e.state.clone().map(TryInto::try_into).transpose().ok()?;
+                let state = state.as_ref().map(ValidCString::as_valid_cstr);
+                EventAny::new_game_state(state).with_ids(&e.base)
+            }
+            OwnedEventEnum::GameMove(e) => {
+                EventAny::new_game_move(e.player, e.code).with_ids(&e.base)
+            }
+            OwnedEventEnum::ChatMsg(e) => {
+                EventAny::chat_msg(e.msg_id, e.author, e.timestamp, &e.message).with_ids(&e.base)
+            }
+            OwnedEventEnum::Unknown => return None,
+        };
+        Some(event)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedEventGameLoadMethods {
+    pub base: Event,
+    /// Not serialized because the methods are only valid in this process.
+    /// Deserializes to NULL.
+    #[cfg_attr(feature = "serde", serde(skip, default = "std::ptr::null"))]
+    pub methods: *const game_methods,
+    pub init_info: OwnedGameInit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedEventGameState {
+    pub base: Event,
+    pub state: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedEventChat {
+    pub base: Event,
+    pub msg_id: u32,
+    pub author: ClientId,
+    pub timestamp: u64,
+    pub message: String,
+}
+
+type Handler<'h, E> = Option<Box<dyn FnMut(&E) + 'h>>;
+type LoadMethodsHandler<'h> = Option<Box<dyn FnMut(&EventGameLoadMethods<'_>) + 'h>>;
+type StateHandler<'h> = Option<Box<dyn FnMut(&EventGameState<'_>) + 'h>>;
+type ChatHandler<'h> = Option<Box<dyn FnMut(&EventChat<'_>) + 'h>>;
+
+/// Routes events to handlers registered per event type.
+///
+/// This replaces large `match` blocks over [`EventEnum`].
+/// Events without a matching handler go to the [`EventDispatcher::on_other`]
+/// handler if set.
+///
+/// # Example
+/// ```no_run
+/// # use mirabel_sys::event::*;
+/// let mut moves = vec![];
+/// let mut dispatcher = EventDispatcher::new();
+/// dispatcher.on_game_move(|e| moves.push(e.code));
+/// dispatcher.dispatch(&EventAny::new_game_move(PlayerId::new(1), 42));
+/// drop(dispatcher);
+/// assert_eq!(vec![42], moves);
+/// ```
+#[derive(Default)]
+pub struct EventDispatcher<'h> {
+    game_load_methods: LoadMethodsHandler<'h>,
+    game_unload: Handler<'h, Event>,
+    game_state: StateHandler<'h>,
+    game_move: Handler<'h, EventGameMove>,
+    chat_msg: ChatHandler<'h>,
+    other: Handler<'h, EventAny>,
+}
+
+impl<'h> EventDispatcher<'h> {
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn on_game_load_methods(
+        &mut self,
+        handler: impl FnMut(&EventGameLoadMethods<'_>) + 'h,
+    ) -> &mut Self {
+        self.game_load_methods = Some(Box::new(handler));
+        self
+    }
+
+    pub fn on_game_unload(&mut self, handler: impl FnMut(&Event) + 'h) -> &mut Self {
+        self.game_unload = Some(Box::new(handler));
+        self
+    }
+
+    pub fn on_game_state(&mut self, handler: impl FnMut(&EventGameState<'_>) + 'h) -> &mut Self {
+        self.game_state = Some(Box::new(handler));
+        self
+    }
+
+    pub fn on_game_move(&mut self, handler: impl FnMut(&EventGameMove) + 'h) -> &mut Self {
+        self.game_move = Some(Box::new(handler));
+        self
+    }
+
+    pub fn on_chat_msg(&mut self, handler: impl FnMut(&EventChat<'_>) + 'h) -> &mut Self {
+        self.chat_msg = Some(Box::new(handler));
+        self
+    }
+
+    /// Handler for all events without a more specific handler.
+    pub fn on_other(&mut self, handler: impl FnMut(&EventAny) + 'h) -> &mut Self {
+        self.other = Some(Box::new(handler));
+        self
+    }
+
+    /// Call the handler registered for `event`.
+    ///
+    /// Returns `false` if no handler was called.
+    pub fn dispatch(&mut self, event: &EventAny) -> bool {
+        let called = match event.to_rust() {
+            EventEnum::GameLoadMethods(e) => call(&mut self.game_load_methods, &e),
+            EventEnum::GameUnload(e) => call(&mut self.game_unload, &e),
+            EventEnum::GameState(e) => call(&mut self.game_state, &e),
+            EventEnum::GameMove(e) => call(&mut self.game_move, &e),
+            EventEnum::ChatMsg(e) => call(&mut self.chat_msg, &e),
+            EventEnum::Unknown => false,
+        };
+        called || call(&mut self.other, event)
+    }
+}
+
+fn call<E: ?Sized, H: FnMut(&E) + ?Sized>(handler: &mut Option<Box<H>>, event: &E) -> bool {
+    match handler {
+        Some(h) => {
+            h(event);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Removes superseded events from a batch, e.g., all events of one frame.
+///
+/// An event is superseded if the next event in the batch has the same type
+/// and lobby and this type is configured to replace older events.
+/// By default, only [`EVENT_TYPE_E_EVENT_TYPE_GAME_STATE`] is coalesced.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::event::{Coalescer, EventAny};
+/// fn process_frame(events: &mut Vec<EventAny>) {
+///     Coalescer::new().coalesce(events);
+///     // Only the newest of several consecutive states is left.
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Coalescer {
+    types: Vec<EVENT_TYPE>,
+}
+
+impl Coalescer {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_types(&[EVENT_TYPE_E_EVENT_TYPE_GAME_STATE])
+    }
+
+    /// Coalesce only the events of `types`.
+    #[inline]
+    pub fn with_types(types: &[EVENT_TYPE]) -> Self {
+        Self {
+            types: types.to_vec(),
+        }
+    }
+
+    /// Drop all superseded events in `events`, keeping the newest.
+    pub fn coalesce(&self, events: &mut Vec<EventAny>) {
+        let superseded: Vec<_> = events
+            .windows(2)
+            .map(|pair| {
+                let (old, new) = unsafe { (pair[0].base, pair[1].base) };
+                old.type_ == new.type_
+                    && old.lobby_id == new.lobby_id
+                    && self.types.contains(&old.type_)
+            })
+            .collect();
+        let mut index = 0;
+        events.retain(|_| {
+            let keep = !superseded.get(index).copied().unwrap_or(false);
+            index += 1;
+            keep
+        });
+    }
+}
+
+impl Default for Coalescer {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
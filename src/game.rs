@@ -0,0 +1,744 @@
+//! Caller-side wrapper around [`game`], e.g., for frontends and engines.
+
+use std::{
+    ffi::c_char,
+    fmt::{Debug, Display},
+    ptr::{self, null, null_mut},
+};
+
+use crate::{
+    blob::Blob,
+    cstr_to_rust,
+    error::{code_to_result, Error, ErrorCode, ErrorString, Result},
+    game_features::GameFeatures,
+    game_init::GameInit,
+    player::PlayerId,
+    random::Seed,
+    sys::{error_code, game, game_methods, move_code, MOVE_NONE},
+    version::SemVer,
+    ValidCString,
+};
+
+/// Borrowed [`game_methods`] which are known to be valid.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::game::GameMethodsRef;
+/// fn describe(methods: GameMethodsRef) -> String {
+///     format!("{}.{}.{} {}", methods.game_name(), methods.variant_name(),
+///         methods.impl_name(), methods.version())
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct GameMethodsRef<'l>(&'l game_methods);
+
+impl<'l> GameMethodsRef<'l> {
+    /// Wrap `methods`.
+    ///
+    /// # Safety
+    /// All strings and function pointers of `methods` must be valid for
+    /// `'l`.
+    #[inline]
+    pub unsafe fn new(methods: &'l game_methods) -> Self {
+        Self(methods)
+    }
+
+    /// Same as [`GameMethodsRef::new`] but returns [`None`] for NULL.
+    ///
+    /// # Safety
+    /// See [`GameMethodsRef::new`].
+    #[inline]
+    pub unsafe fn from_ptr(methods: *const game_methods) -> Option<Self> {
+        methods.as_ref().map(|m| Self::new(m))
+    }
+
+    /// Name of the game or an empty string if missing.
+    #[inline]
+    pub fn game_name(self) -> &'l str {
+        unsafe { cstr_to_rust(self.0.game_name) }.unwrap_or_default()
+    }
+
+    #[inline]
+    pub fn variant_name(self) -> &'l str {
+        unsafe { cstr_to_rust(self.0.variant_name) }.unwrap_or_default()
+    }
+
+    #[inline]
+    pub fn impl_name(self) -> &'l str {
+        unsafe { cstr_to_rust(self.0.impl_name) }.unwrap_or_default()
+    }
+
+    #[inline]
+    pub fn version(self) -> SemVer {
+        self.0.version.into()
+    }
+
+    #[inline]
+    pub fn features(self) -> GameFeatures {
+        self.0.features.into()
+    }
+
+    #[inline]
+    pub fn as_raw(self) -> &'l game_methods {
+        self.0
+    }
+
+    #[inline]
+    pub fn as_ptr(self) -> *const game_methods {
+        self.0
+    }
+
+    /// Create a new game from these methods and `init`.
+    ///
+    /// # Safety
+    /// The game must not outlive `'l` because [`Game`] does not carry a
+    /// lifetime, e.g., it must be dropped before the plugin is unloaded.
+    pub unsafe fn create(self, init: GameInit) -> Result<Game> {
+        Game::new(self.0, init)
+    }
+}
+
+impl<'l> Debug for GameMethodsRef<'l> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GameMethodsRef")
+            .field("game_name", &self.game_name())
+            .field("variant_name", &self.variant_name())
+            .field("impl_name", &self.impl_name())
+            .field("version", &self.version())
+            .finish()
+    }
+}
+
+/// An owned game instance created through its [`game_methods`].
+///
+/// The game is destroyed on drop.
+/// Errors returned by the methods include the message from
+/// `get_last_error`.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{game::Game, game_init::GameInit, player::PlayerId, sys::game_methods};
+/// fn first_move(methods: &'static game_methods) -> mirabel_sys::error::Result<String> {
+///     let mut game = unsafe { Game::new(methods, GameInit::Default)? };
+///     let code = game.str_to_move(PlayerId::new(1), "e4")?;
+///     let mut string = String::new();
+///     game.move_to_str(PlayerId::new(1), code, &mut string)?;
+///     Ok(string)
+/// }
+/// ```
+pub struct Game {
+    game: game,
+}
+
+impl Game {
+    /// Create a new game from `methods` and `init`.
+    ///
+    /// # Safety
+    /// `methods` must be valid for the whole lifetime of the game.
+    pub unsafe fn new(methods: *const game_methods, init: GameInit) -> Result<Self> {
+        let mut init = init.into_sys().map_err(|_| {
+            Error::new_static(ErrorCode::InvalidInput, "init string contains NUL\0")
+        })?;
+        let create = (*methods).create.expect("create missing");
+        let mut game = Self {
+            game: game {
+                methods,
+                data1: null_mut(),
+                data2: null_mut(),
+                ..Default::default()
+            },
+        };
+        let code = create(&mut game.game, init.as_mut_ptr());
+        // Even a failed create must be followed by destroy on drop.
+        game.check(code)?;
+        Ok(game)
+    }
+
+    /// Create an independent copy of this game.
+    pub fn try_clone(&mut self) -> Result<Self> {
+        let clone = self.method(self.methods().clone)?;
+        let mut target = Self {
+            game: game {
+                methods: self.game.methods,
+                data1: null_mut(),
+                data2: null_mut(),
+                ..Default::default()
+            },
+        };
+        let result = unsafe { clone(&mut self.game, &mut target.game) };
+        self.check(result)?;
+        Ok(target)
+    }
+
+    /// Overwrite this game with the state of `other`.
+    ///
+    /// Both games must use the same methods.
+    /// Games without `copy_from` fall back to re-importing the exported
+    /// state.
+    ///
+    /// # Panics
+    /// Panics if the methods differ.
+    pub fn copy_from(&mut self, other: &mut Self) -> Result<()> {
+        assert!(self.same_methods(other), "copying between different games");
+        match self.methods().copy_from {
+            Some(copy_from) => {
+                let result = unsafe { copy_from(&mut self.game, &mut other.game) };
+                self.check(result)
+            }
+            None => {
+                let mut state = String::new();
+                other.export_state(&mut state)?;
+                self.import_state(Some(&state))
+            }
+        }
+    }
+
+    /// Returns `true` if both games use the same methods.
+    #[inline]
+    pub fn same_methods(&self, other: &Self) -> bool {
+        ptr::eq(self.game.methods, other.game.methods)
+    }
+
+    #[inline]
+    pub fn methods(&self) -> &game_methods {
+        unsafe { &*self.game.methods }
+    }
+
+    #[inline]
+    pub fn methods_ref(&self) -> GameMethodsRef<'_> {
+        // The methods are valid for the lifetime of the game.
+        unsafe { GameMethodsRef::new(self.methods()) }
+    }
+
+    /// The underlying [`game`], e.g., for calling methods not wrapped here.
+    ///
+    /// The game must not be destroyed through this pointer.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut game {
+        &mut self.game
+    }
+
+    /// Import a state string or reset to the initial state for [`None`].
+    pub fn import_state(&mut self, state: Option<&str>) -> Result<()> {
+        let import_state = self.method(self.methods().import_state)?;
+        let state = state.map(ValidCString::try_from).transpose().map_err(|_| {
+            Error::new_static(ErrorCode::InvalidInput, "state string contains NUL\0")
+        })?;
+        let state = state.as_ref().map_or(null(), |s| s.as_ptr());
+        let result = unsafe { import_state(&mut self.game, state) };
+        self.check(result)
+    }
+
+    /// Write the current state string into `buf`, replacing its contents.
+    pub fn export_state(&mut self, buf: &mut String) -> Result<()> {
+        let export_state = self.method(self.methods().export_state)?;
+        let capacity = self.game.sizer.state_str;
+        self.read_str(capacity, buf, |game, size, string| unsafe {
+            export_state(game, size, string)
+        })
+    }
+
+    /// Serialize the current state, e.g., for [`GameInit::Serialized`].
+    ///
+    /// The [`buf_sizer`](crate::sys::buf_sizer) has no size for serialized
+    /// games, so the buffer starts at the size of state strings.
+    /// While the game fails with [`ErrorCode::OutOfMemory`], the buffer is
+    /// grown to the reported size or doubled, up to [`MAX_SERIALIZED`]
+    /// bytes.
+    pub fn serialize(&mut self) -> Result<Blob<'static>> {
+        let serialize = self.method(self.methods().serialize)?;
+        let mut capacity = self.game.sizer.state_str.max(64);
+        loop {
+            let mut buf = vec![0u8; capacity];
+            let mut size = buf.len();
+            let result = unsafe { serialize(&mut self.game, &mut size, buf.as_mut_ptr().cast()) };
+            if code_to_result(result) == Err(ErrorCode::OutOfMemory) && capacity < MAX_SERIALIZED {
+                capacity = size.max(capacity * 2).min(MAX_SERIALIZED);
+                continue;
+            }
+            self.check(result)?;
+            buf.truncate(size);
+            return Ok(buf.into());
+        }
+    }
+
+    /// Hash of the current state, e.g., for transposition tables.
+    ///
+    /// Equal states have equal IDs, but different states may collide.
+    pub fn id(&mut self) -> Result<u64> {
+        let id = self.method(self.methods().id)?;
+        let mut ret = 0;
+        let result = unsafe { id(&mut self.game, &mut ret) };
+        self.check(result)?;
+        Ok(ret)
+    }
+
+    /// Heuristic evaluation of the current state from the perspective of
+    /// `player`.
+    ///
+    /// This is always an [`Eval::Score`].
+    pub fn eval(&mut self, player: PlayerId) -> Result<Eval> {
+        let eval = self.method(self.methods().eval)?;
+        let mut ret = 0.0;
+        let result = unsafe { eval(&mut self.game, player.into(), &mut ret) };
+        self.check(result)?;
+        Ok(Eval::Score(ret))
+    }
+
+    /// Resolve hidden and random information using `seed`.
+    ///
+    /// Afterwards, the game is in one concrete state consistent with the
+    /// information available before, e.g., for determinized searches.
+    pub fn discretize(&mut self, seed: Seed) -> Result<()> {
+        let discretize = self.method(self.methods().discretize)?;
+        let result = unsafe { discretize(&mut self.game, seed.into()) };
+        self.check(result)
+    }
+
+    /// Remove all information which is hidden from every player not in
+    /// `players`.
+    ///
+    /// The state string of the redacted game can then be sent to these
+    /// players.
+    ///
+    /// # Panics
+    /// Panics if `players` contains more than 255 players.
+    pub fn redact_keep_state(&mut self, players: &[PlayerId]) -> Result<()> {
+        let redact_keep_state = self.method(self.methods().redact_keep_state)?;
+        let count = players.len().try_into().expect("too many players");
+        let mut players = PlayerId::as_raw_slice(players).to_vec();
+        let result = unsafe { redact_keep_state(&mut self.game, count, players.as_mut_ptr()) };
+        self.check(result)
+    }
+
+    /// Results of a finished game.
+    ///
+    /// Only meaningful once [`Game::players_to_move`] is empty.
+    pub fn results(&mut self) -> Result<GameResults> {
+        let get_results = self.method(self.methods().get_results)?;
+        let mut players = vec![PlayerId::NONE; self.game.sizer.max_results.into()];
+        let mut count = 0;
+        let result =
+            unsafe { get_results(&mut self.game, &mut count, players.as_mut_ptr().cast()) };
+        self.check(result)?;
+        players.truncate(count.into());
+        Ok(GameResults::new(players))
+    }
+
+    /// Export the legacy of a finished game for the next game of a match.
+    ///
+    /// Pass it to [`GameInit::with_legacy`] when creating the next game.
+    pub fn export_legacy(&mut self) -> Result<Legacy> {
+        let export_legacy = self.method(self.methods().export_legacy)?;
+        let capacity = self.game.sizer.legacy_str;
+        let mut legacy = String::new();
+        self.read_str(capacity, &mut legacy, |game, size, string| unsafe {
+            export_legacy(game, size, string)
+        })?;
+        Ok(Legacy(legacy))
+    }
+
+    /// Players which currently have to move.
+    ///
+    /// This is empty once the game is over.
+    pub fn players_to_move(&mut self) -> Result<Vec<PlayerId>> {
+        let players_to_move = self.method(self.methods().players_to_move)?;
+        let mut players = vec![PlayerId::NONE; self.game.sizer.max_players_to_move.into()];
+        let mut count = 0;
+        let result =
+            unsafe { players_to_move(&mut self.game, &mut count, players.as_mut_ptr().cast()) };
+        self.check(result)?;
+        players.truncate(count.into());
+        Ok(players)
+    }
+
+    /// All concrete moves which `player` can currently make.
+    pub fn concrete_moves(&mut self, player: PlayerId) -> Result<Vec<move_code>> {
+        let get_concrete_moves = self.method(self.methods().get_concrete_moves)?;
+        let mut moves = vec![MOVE_NONE; self.game.sizer.max_moves.try_into().unwrap()];
+        let mut count = 0;
+        let result = unsafe {
+            get_concrete_moves(
+                &mut self.game,
+                player.into(),
+                &mut count,
+                moves.as_mut_ptr(),
+            )
+        };
+        self.check(result)?;
+        moves.truncate(count.try_into().unwrap());
+        Ok(moves)
+    }
+
+    /// Succeeds if `player` may currently play `code`.
+    ///
+    /// The error explains why the move is illegal.
+    pub fn check_legal_move(&mut self, player: PlayerId, code: move_code) -> Result<()> {
+        let is_legal_move = self.method(self.methods().is_legal_move)?;
+        let result = unsafe { is_legal_move(&mut self.game, player.into(), code) };
+        self.check(result)
+    }
+
+    pub fn make_move(&mut self, player: PlayerId, code: move_code) -> Result<()> {
+        let make_move = self.method(self.methods().make_move)?;
+        let result = unsafe { make_move(&mut self.game, player.into(), code) };
+        self.check(result)
+    }
+
+    /// Action which other players observe when `code` is played.
+    ///
+    /// For games with randomness or hidden information, this redacts the
+    /// parts of the move which are not visible to everyone.
+    pub fn move_to_action(&mut self, code: move_code) -> Result<Action> {
+        let move_to_action = self.method(self.methods().move_to_action)?;
+        let mut ret = MOVE_NONE;
+        let result = unsafe { move_to_action(&mut self.game, code, &mut ret) };
+        self.check(result)?;
+        Ok(Action(ret))
+    }
+
+    /// Returns `true` if `code` is an action rather than a concrete move.
+    pub fn is_action(&mut self, code: move_code) -> Result<bool> {
+        let is_action = self.method(self.methods().is_action)?;
+        let mut ret = false;
+        let result = unsafe { is_action(&mut self.game, code, &mut ret) };
+        self.check(result)?;
+        Ok(ret)
+    }
+
+    /// All actions which `player` can currently take.
+    pub fn actions(&mut self, player: PlayerId) -> Result<Vec<Action>> {
+        let get_actions = self.method(self.methods().get_actions)?;
+        let mut actions = vec![Action::NONE; self.game.sizer.max_actions.try_into().unwrap()];
+        let mut count = 0;
+        let result = unsafe {
+            get_actions(
+                &mut self.game,
+                player.into(),
+                &mut count,
+                actions.as_mut_ptr().cast(),
+            )
+        };
+        self.check(result)?;
+        actions.truncate(count.try_into().unwrap());
+        Ok(actions)
+    }
+
+    /// Write the string for `code` into `buf`, replacing its contents.
+    ///
+    /// [`MOVE_NONE`] results in an empty string.
+    pub fn move_to_str(
+        &mut self,
+        player: PlayerId,
+        code: move_code,
+        buf: &mut String,
+    ) -> Result<()> {
+        buf.clear();
+        if code == MOVE_NONE {
+            return Ok(());
+        }
+
+        let get_move_str = self.method(self.methods().get_move_str)?;
+        let capacity = self.game.sizer.move_str;
+        self.read_str(capacity, buf, |game, size, string| unsafe {
+            get_move_str(game, player.into(), code, size, string)
+        })
+    }
+
+    /// Parse a move string of `player`, e.g., entered by a user.
+    pub fn str_to_move(&mut self, player: PlayerId, string: &str) -> Result<move_code> {
+        let get_move_code = self.method(self.methods().get_move_code)?;
+        let string = ValidCString::try_from(string).map_err(|_| {
+            Error::new_static(ErrorCode::InvalidInput, "move string contains NUL\0")
+        })?;
+        let mut code = MOVE_NONE;
+        let result =
+            unsafe { get_move_code(&mut self.game, player.into(), string.as_ptr(), &mut code) };
+        self.check(result)?;
+        Ok(code)
+    }
+
+    /// Call `method` with a buffer of `capacity` bytes and copy the written
+    /// string into `buf`.
+    fn read_str(
+        &mut self,
+        capacity: usize,
+        buf: &mut String,
+        method: impl FnOnce(*mut game, *mut usize, *mut c_char) -> error_code,
+    ) -> Result<()> {
+        buf.clear();
+        // Leave room for the NUL terminator.
+        let mut bytes = vec![0u8; capacity + 1];
+        let mut size = bytes.len();
+        let result = method(&mut self.game, &mut size, bytes.as_mut_ptr().cast());
+        self.check(result)?;
+
+        bytes.truncate(size.min(bytes.len()));
+        if let Some(end) = bytes.iter().position(|b| *b == 0) {
+            bytes.truncate(end);
+        }
+        buf.push_str(&String::from_utf8_lossy(&bytes));
+        Ok(())
+    }
+
+    /// Returns the method or fails with [`ErrorCode::FeatureUnsupported`].
+    #[inline]
+    fn method<F>(&self, method: Option<F>) -> Result<F> {
+        method.ok_or_else(|| ErrorCode::FeatureUnsupported.into())
+    }
+
+    /// Convert `code` into a [`Result`] with the last error message.
+    fn check(&mut self, code: error_code) -> Result<()> {
+        code_to_result(code).map_err(|code| {
+            let message = self
+                .methods()
+                .get_last_error
+                .and_then(|get_last_error| unsafe { cstr_to_rust(get_last_error(&mut self.game)) });
+            match message {
+                Some(message) => Error::new_dynamic(code, message.to_string()),
+                None => Error {
+                    code,
+                    message: ErrorString::None,
+                },
+            }
+        })
+    }
+}
+
+/// Largest buffer offered to the `serialize` game method, see
+/// [`Game::serialize`].
+pub const MAX_SERIALIZED: usize = 1 << 30;
+
+/// A move as observed by the other players.
+///
+/// In games with randomness or hidden information, a concrete move contains
+/// details which are not visible to everybody, e.g., which card was drawn.
+/// [`Game::move_to_action`] strips these details.
+/// Use [`Action::get`] to pass an action where a [`move_code`] is expected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
+pub struct Action(move_code);
+
+impl Action {
+    pub const NONE: Self = Self(MOVE_NONE);
+
+    /// Treat `code` as an action without checking it.
+    ///
+    /// Use [`Game::is_action`] to check codes from untrusted sources.
+    #[inline]
+    pub const fn new_unchecked(code: move_code) -> Self {
+        Self(code)
+    }
+
+    #[inline]
+    pub const fn get(self) -> move_code {
+        self.0
+    }
+}
+
+impl From<Action> for move_code {
+    #[inline]
+    fn from(action: Action) -> Self {
+        action.0
+    }
+}
+
+/// Results of a finished game as returned by `get_results`.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{game::*, player::PlayerId};
+/// let results = GameResults::new(vec![PlayerId::new(2)]);
+/// assert_eq!(Outcome::Loss, results.outcome(PlayerId::new(1)));
+/// assert_eq!(Outcome::Win, results.outcome(PlayerId::new(2)));
+/// assert_eq!(Outcome::Draw, GameResults::Draw.outcome(PlayerId::new(1)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameResults {
+    /// Nobody won.
+    Draw,
+    Winners(Vec<PlayerId>),
+}
+
+impl GameResults {
+    /// Interpret the winning players, where no winners mean a draw.
+    pub fn new(winners: Vec<PlayerId>) -> Self {
+        if winners.is_empty() {
+            Self::Draw
+        } else {
+            Self::Winners(winners)
+        }
+    }
+
+    #[inline]
+    pub fn is_draw(&self) -> bool {
+        matches!(self, Self::Draw)
+    }
+
+    /// Winning players, empty for a draw.
+    #[inline]
+    pub fn winners(&self) -> &[PlayerId] {
+        match self {
+            Self::Draw => &[],
+            Self::Winners(winners) => winners,
+        }
+    }
+
+    /// Outcome of the game from the perspective of `player`.
+    pub fn outcome(&self, player: PlayerId) -> Outcome {
+        match self {
+            Self::Draw => Outcome::Draw,
+            Self::Winners(winners) if winners.contains(&player) => Outcome::Win,
+            Self::Winners(_) => Outcome::Loss,
+        }
+    }
+}
+
+/// Outcome of a finished game for a single player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl Outcome {
+    /// Terminal evaluation for searches: 1 for a win, 0 for a draw and -1
+    /// for a loss.
+    ///
+    /// A draw is worth 0 on the same scale as [`Eval::Score`], see
+    /// [`Eval`].
+    #[inline]
+    pub fn value(self) -> f32 {
+        match self {
+            Outcome::Win => 1.0,
+            Outcome::Draw => 0.0,
+            Outcome::Loss => -1.0,
+        }
+    }
+}
+
+/// Evaluation of a state, e.g., in a search.
+///
+/// The `eval` game method only reports heuristic scores, so
+/// [`Game::eval`] always returns [`Eval::Score`].
+/// Searches can use the other variants for proven results, e.g., from
+/// [`Outcome`]s.
+///
+/// Evaluations are ordered with losses below all scores and wins above all
+/// scores.
+/// A draw is worth 0, like in [`Outcome::value`].
+/// It ranks directly above a score of zero without equaling it, so proven
+/// draws are preferred over even heuristic scores.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::game::*;
+/// assert!(Eval::Loss < Eval::Score(-100.0) && Eval::Score(100.0) < Eval::Win);
+/// assert!(Eval::Draw < Eval::Score(0.5) && Eval::Score(-0.5) < Eval::Draw);
+/// assert!(Eval::Score(0.0) < Eval::Draw);
+/// assert_ne!(Eval::Draw, Eval::Score(0.0));
+/// assert!(Eval::from(Outcome::Draw).is_proven());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub enum Eval {
+    /// Proven loss.
+    Loss,
+    /// Heuristic score where higher is better, e.g., in centipawns.
+    Score(f32),
+    /// Proven draw.
+    Draw,
+    /// Proven win.
+    Win,
+}
+
+impl Eval {
+    /// Returns `true` for proven wins, losses and draws.
+    #[inline]
+    pub fn is_proven(self) -> bool {
+        !matches!(self, Self::Score(_))
+    }
+
+    /// Rank of the variant, the score within it and whether it is proven.
+    fn key(self) -> (u8, f32, bool) {
+        match self {
+            Self::Loss => (0, 0.0, true),
+            Self::Score(score) => (1, score, false),
+            Self::Draw => (1, Outcome::Draw.value(), true),
+            Self::Win => (2, 0.0, true),
+        }
+    }
+}
+
+impl PartialEq for Eval {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl PartialOrd for Eval {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.key().partial_cmp(&other.key())
+    }
+}
+
+impl From<Outcome> for Eval {
+    #[inline]
+    fn from(outcome: Outcome) -> Self {
+        match outcome {
+            Outcome::Win => Self::Win,
+            Outcome::Draw => Self::Draw,
+            Outcome::Loss => Self::Loss,
+        }
+    }
+}
+
+/// Legacy string which carries results over between games of a match.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{game::Legacy, game_init::GameInit};
+/// let legacy = Legacy::from("score=1:0".to_string());
+/// let init = GameInit::with_legacy(Some("9x9"), &legacy);
+/// assert!(matches!(init, GameInit::Standard { legacy: Some("score=1:0"), .. }));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Legacy(String);
+
+impl Legacy {
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    #[inline]
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl From<String> for Legacy {
+    #[inline]
+    fn from(legacy: String) -> Self {
+        Self(legacy)
+    }
+}
+
+impl Display for Legacy {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl Drop for Game {
+    fn drop(&mut self) {
+        if let Some(destroy) = self.methods().destroy {
+            unsafe { destroy(&mut self.game) };
+        }
+    }
+}
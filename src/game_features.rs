@@ -0,0 +1,145 @@
+//! Rust version of [`game_feature_flags`].
+
+use std::ops::{BitOr, BitOrAssign};
+
+use crate::sys::{__BindgenBitfieldUnit, game_feature_flags};
+
+/// Set of _surena_ game features.
+///
+/// Unlike [`game_feature_flags`], this can be built in `const` contexts.
+/// Each feature uses the bit of its field in [`game_feature_flags`].
+/// Bits 7 and 9 are reserved for the unwrapped `sync_data` and `scores`
+/// fields.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{game_features::GameFeatures, sys::game_feature_flags};
+/// const FEATURES: GameFeatures = GameFeatures::new().options(true).random_moves(true);
+/// const FLAGS: game_feature_flags = FEATURES.to_sys();
+/// assert!(FLAGS.options() && !FLAGS.legacy());
+/// assert!(FEATURES.contains(GameFeatures::OPTIONS));
+/// assert!(!FEATURES.contains(GameFeatures::LEGACY));
+///
+/// let flags = game_feature_flags::from(FEATURES);
+/// assert_eq!(FEATURES, flags.into());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GameFeatures(u32);
+
+macro_rules! features {
+    ($($bit:expr => $constant:ident, $name:ident;)*) => {
+        impl GameFeatures {
+            $(pub const $constant: Self = Self(1 << $bit);)*
+
+            $(
+                /// Enable or disable this feature.
+                #[inline]
+                pub const fn $name(self, enable: bool) -> Self {
+                    self.set(Self::$constant, enable)
+                }
+            )*
+        }
+
+        impl From<game_feature_flags> for GameFeatures {
+            fn from(flags: game_feature_flags) -> Self {
+                GameFeatures::new()$(.$name(flags.$name()))*
+            }
+        }
+    };
+}
+
+features! {
+    0 => ERROR_STRINGS, error_strings;
+    1 => OPTIONS, options;
+    2 => SERIALIZABLE, serializable;
+    3 => LEGACY, legacy;
+    4 => RANDOM_MOVES, random_moves;
+    5 => HIDDEN_INFORMATION, hidden_information;
+    6 => SIMULTANEOUS_MOVES, simultaneous_moves;
+    8 => MOVE_ORDERING, move_ordering;
+    10 => ID, id;
+    11 => EVAL, eval;
+    12 => PLAYOUT, playout;
+    13 => PRINT, print;
+    14 => BIG_MOVES, big_moves;
+}
+
+impl GameFeatures {
+    /// Create an empty feature set.
+    #[inline]
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    /// Returns `true` if all features of `other` are enabled.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    #[inline]
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Enable or disable all features of `other`.
+    #[inline]
+    pub const fn set(self, other: Self, enable: bool) -> Self {
+        if enable {
+            self.union(other)
+        } else {
+            Self(self.0 & !other.0)
+        }
+    }
+
+    /// Convert into [`game_feature_flags`], e.g., for a `static`
+    /// [`game_methods`](crate::sys::game_methods).
+    #[inline]
+    pub const fn to_sys(self) -> game_feature_flags {
+        game_feature_flags {
+            _bitfield_align_1: [],
+            _bitfield_1: __BindgenBitfieldUnit::new(bitfield_storage(self.0)),
+        }
+    }
+}
+
+/// Storage of a bindgen bitfield unit with the given `bits` set.
+///
+/// Mirrors the bit order of `__BindgenBitfieldUnit::get_bit`.
+const fn bitfield_storage<const N: usize>(bits: u32) -> [u8; N] {
+    let mut storage = [0; N];
+    let mut i = 0;
+    while i < N && i < 4 {
+        let byte = (bits >> (8 * i)) as u8;
+        storage[i] = if cfg!(target_endian = "big") {
+            byte.reverse_bits()
+        } else {
+            byte
+        };
+        i += 1;
+    }
+    storage
+}
+
+impl From<GameFeatures> for game_feature_flags {
+    #[inline]
+    fn from(features: GameFeatures) -> Self {
+        features.to_sys()
+    }
+}
+
+impl BitOr for GameFeatures {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl BitOrAssign for GameFeatures {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.union(rhs);
+    }
+}
@@ -1,18 +1,29 @@
 //! Wrapper around [`game_init`].
 
-use std::slice::from_raw_parts;
+use std::{
+    ffi::{c_char, NulError},
+    ops::Deref,
+    ptr::null,
+};
 
 use crate::{
+    blob::{slice_from_raw, Blob},
     cstr_to_rust,
+    error::{self, Error, ErrorCode},
+    game::Legacy,
     sys::{
         game_init, GAME_INIT_SOURCE_TYPE_E_GAME_INIT_SOURCE_TYPE_DEFAULT as SOURCE_TYPE_DEFAULT,
         GAME_INIT_SOURCE_TYPE_E_GAME_INIT_SOURCE_TYPE_SERIALIZED as SOURCE_TYPE_SERIALIZED,
         GAME_INIT_SOURCE_TYPE_E_GAME_INIT_SOURCE_TYPE_STANDARD as SOURCE_TYPE_STANDARD,
     },
+    ValidCString,
 };
 
 /// Rust version of [`game_init`] borrowing the referenced data structures.
-#[derive(Debug, Copy, Clone)]
+///
+/// With the `arbitrary` feature, this can be generated from fuzzer input.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum GameInit<'l> {
     Default,
     Standard {
@@ -20,16 +31,40 @@ pub enum GameInit<'l> {
         legacy: Option<&'l str>,
         state: Option<&'l str>,
     },
-    Serialized(&'l [u8]),
+    Serialized(Blob<'l>),
 }
 
 impl<'l> GameInit<'l> {
     /// Create a new [`GameInit`] from a [`game_init`].
     ///
+    /// Fails with [`ErrorCode::InvalidInput`] if the pointers of a
+    /// serialized buffer do not form a valid pair, see [`Blob::from_raw`].
+    ///
     /// # Safety
     /// The supplied `init_info` must be valid.
-    pub unsafe fn new(init_info: &game_init) -> Self {
-        match init_info.source_type {
+    ///
+    /// # Example
+    /// ```
+    /// # use mirabel_sys::{game_init::GameInit, sys::{self, game_init}};
+    /// let buf = [1u8, 2, 3];
+    /// let mut init = game_init::default();
+    /// init.source_type = sys::GAME_INIT_SOURCE_TYPE_E_GAME_INIT_SOURCE_TYPE_SERIALIZED;
+    /// init.source.serialized.buf_begin = buf.as_ptr_range().start.cast();
+    /// init.source.serialized.buf_end = buf.as_ptr_range().end.cast();
+    ///
+    /// let GameInit::Serialized(serialized) = (unsafe { GameInit::new(&init) }.unwrap()) else {
+    ///     panic!("expected serialized init");
+    /// };
+    /// assert_eq!(&[1, 2, 3], serialized.as_slice());
+    ///
+    /// let init = GameInit::Serialized(serialized).into_sys().unwrap();
+    /// let GameInit::Serialized(serialized) = (unsafe { GameInit::new(&init) }.unwrap()) else {
+    ///     panic!("expected serialized init");
+    /// };
+    /// assert_eq!(&[1, 2, 3], serialized.as_slice());
+    /// ```
+    pub unsafe fn new(init_info: &game_init) -> error::Result<Self> {
+        Ok(match init_info.source_type {
             SOURCE_TYPE_DEFAULT => Self::Default,
             SOURCE_TYPE_STANDARD => {
                 let source = init_info.source.standard;
@@ -41,14 +76,211 @@ impl<'l> GameInit<'l> {
             }
             SOURCE_TYPE_SERIALIZED => {
                 let source = init_info.source.serialized;
-                let begin: *const u8 = source.buf_begin.cast::<u8>();
-                let end: *const u8 = source.buf_begin.cast::<u8>();
-                Self::Serialized(from_raw_parts(
-                    begin,
-                    end.offset_from(begin).try_into().unwrap(),
-                ))
+                let buf = slice_from_raw(source.buf_begin, source.buf_end).ok_or_else(|| {
+                    Error::new_static(ErrorCode::InvalidInput, "invalid serialized buffer\0")
+                })?;
+                Self::Serialized(Blob::borrowed(buf))
             }
             _ => unreachable!("unexpected SOURCE_TYPE"),
+        })
+    }
+
+    /// Create a [`GameInit::Standard`] from the given strings.
+    #[inline]
+    pub fn standard(
+        opts: Option<&'l str>,
+        legacy: Option<&'l str>,
+        state: Option<&'l str>,
+    ) -> Self {
+        Self::Standard {
+            opts,
+            legacy,
+            state,
+        }
+    }
+
+    /// Create a [`GameInit::Standard`] for the next game of a match.
+    #[inline]
+    pub fn with_legacy(opts: Option<&'l str>, legacy: &'l Legacy) -> Self {
+        Self::standard(opts, Some(legacy.as_str()), None)
+    }
+
+    /// Convert into a [`game_init`] which can be passed to the C API.
+    ///
+    /// The strings are copied into NUL-terminated buffers owned by the
+    /// returned `SysGameInit`.
+    /// A serialized buffer is moved or kept borrowed.
+    /// Fails if one of the strings contains a NUL byte.
+    ///
+    /// # Example
+    /// ```
+    /// # use mirabel_sys::game_init::GameInit;
+    /// let init = GameInit::standard(Some("9x9"), None, None).into_sys().unwrap();
+    /// let GameInit::Standard { opts, legacy, .. } = (unsafe { GameInit::new(&init) }.unwrap()) else {
+    ///     panic!("expected standard init");
+    /// };
+    /// assert_eq!(Some("9x9"), opts);
+    /// assert_eq!(None, legacy);
+    /// ```
+    pub fn into_sys(self) -> Result<SysGameInit<'l>, NulError> {
+        let mut init = game_init::default();
+        let mut strings: [Option<ValidCString>; 3] = Default::default();
+        let mut serialized = None;
+        match self {
+            GameInit::Default => init.source_type = SOURCE_TYPE_DEFAULT,
+            GameInit::Standard {
+                opts,
+                legacy,
+                state,
+            } => {
+                init.source_type = SOURCE_TYPE_STANDARD;
+                for (string, owned) in [opts, legacy, state].into_iter().zip(&mut strings) {
+                    *owned = string.map(|s| s.to_string().try_into()).transpose()?;
+                }
+                let [opts, legacy, state] = &strings;
+                init.source.standard.opts = as_ptr(opts);
+                init.source.standard.legacy = as_ptr(legacy);
+                init.source.standard.state = as_ptr(state);
+            }
+            GameInit::Serialized(buf) => {
+                init.source_type = SOURCE_TYPE_SERIALIZED;
+                // Moving an owned buffer does not move its contents.
+                let range = buf.as_slice().as_ptr_range();
+                init.source.serialized.buf_begin = range.start.cast();
+                init.source.serialized.buf_end = range.end.cast();
+                serialized = Some(buf);
+            }
         }
+
+        Ok(SysGameInit {
+            init,
+            _strings: strings,
+            _serialized: serialized,
+        })
     }
 }
+
+/// Owned version of [`GameInit`], e.g., for storing it in events.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::game_init::*;
+/// let owned = OwnedGameInit::from(&GameInit::standard(Some("9x9"), None, None));
+/// assert!(matches!(owned.as_game_init(), GameInit::Standard { opts: Some("9x9"), .. }));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OwnedGameInit {
+    #[default]
+    Default,
+    Standard {
+        opts: Option<String>,
+        legacy: Option<String>,
+        state: Option<String>,
+    },
+    Serialized(Blob<'static>),
+}
+
+impl OwnedGameInit {
+    /// Borrow as a [`GameInit`], e.g., to call [`GameInit::into_sys`].
+    pub fn as_game_init(&self) -> GameInit<'_> {
+        match self {
+            OwnedGameInit::Default => GameInit::Default,
+            OwnedGameInit::Standard {
+                opts,
+                legacy,
+                state,
+            } => GameInit::standard(opts.as_deref(), legacy.as_deref(), state.as_deref()),
+            OwnedGameInit::Serialized(buf) => GameInit::Serialized(buf.as_slice().into()),
+        }
+    }
+}
+
+impl<'l> From<&GameInit<'l>> for OwnedGameInit {
+    fn from(init: &GameInit<'l>) -> Self {
+        match init {
+            GameInit::Default => OwnedGameInit::Default,
+            GameInit::Standard {
+                opts,
+                legacy,
+                state,
+            } => OwnedGameInit::Standard {
+                opts: opts.map(str::to_string),
+                legacy: legacy.map(str::to_string),
+                state: state.map(str::to_string),
+            },
+            GameInit::Serialized(buf) => OwnedGameInit::Serialized(buf.to_vec().into()),
+        }
+    }
+}
+
+/// A [`game_init`] which owns the strings it references.
+///
+/// Created by [`GameInit::into_sys`].
+#[doc(hidden)]
+pub struct SysGameInit<'l> {
+    init: game_init,
+    // Referenced by `init` and therefore never touched again.
+    _strings: [Option<ValidCString>; 3],
+    _serialized: Option<Blob<'l>>,
+}
+
+impl<'l> SysGameInit<'l> {
+    /// Pointer for C APIs which want a mutable [`game_init`].
+    ///
+    /// The C side must not modify the referenced strings.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut game_init {
+        &mut self.init
+    }
+}
+
+impl<'l> Deref for SysGameInit<'l> {
+    type Target = game_init;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.init
+    }
+}
+
+fn as_ptr(string: &Option<ValidCString>) -> *const c_char {
+    string.as_ref().map_or(null(), |s| s.as_ptr())
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for GameInit<'a> {
+    /// Generates strings without NUL bytes, so that
+    /// [`GameInit::into_sys`] always succeeds.
+    ///
+    /// # Example
+    /// ```
+    /// # use arbitrary::{Arbitrary, Unstructured};
+    /// # use mirabel_sys::game_init::GameInit;
+    /// let mut u = Unstructured::new(b"\x01\x01\x039\0x\x00\x00");
+    /// let init = GameInit::arbitrary(&mut u).unwrap();
+    /// assert!(init.into_sys().is_ok());
+    /// ```
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=2)? {
+            0 => Self::Default,
+            1 => Self::standard(init_str(u)?, init_str(u)?, init_str(u)?),
+            _ => Self::Serialized(u.arbitrary()?),
+        })
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for OwnedGameInit {
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok((&GameInit::arbitrary(u)?).into())
+    }
+}
+
+/// Arbitrary string cut off at the first NUL byte.
+#[cfg(feature = "arbitrary")]
+fn init_str<'a>(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Option<&'a str>> {
+    let string: Option<&str> = u.arbitrary()?;
+    Ok(string.map(|s| s.find('\0').map_or(s, |end| &s[..end])))
+}
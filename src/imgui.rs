@@ -3,19 +3,29 @@
 //! This can be used to create _ImGui_ UIs in _mirabel_.
 
 use std::{
-    ffi::{c_char, c_void},
+    ffi::{c_char, c_int, c_void, CStr},
     mem,
+    ptr::null_mut,
 };
 
 use crate::{sys, ValidCStr};
 
+#[cfg(feature = "test-stubs")]
+#[doc(hidden)]
+pub mod stub;
+
+#[cfg(feature = "test-stubs")]
+use stub::backend;
+#[cfg(not(feature = "test-stubs"))]
+use sys as backend;
+
 /// Create a line of text in the UI.
 pub fn text(text: &str) {
     let text = text.as_bytes().as_ptr_range();
     let start: *const u8 = text.start;
     let end: *const u8 = text.end;
     unsafe {
-        sys::ImGuiThin_TextUnformatted(start.cast::<c_char>(), end.cast::<c_char>());
+        backend::ImGuiThin_TextUnformatted(start.cast::<c_char>(), end.cast::<c_char>());
     }
 }
 
@@ -23,14 +33,14 @@ pub fn text(text: &str) {
 ///
 /// This returns true once if pressed.
 pub fn button(label: ValidCStr) -> bool {
-    unsafe { sys::ImGuiThin_Button(label.into()) }
+    unsafe { backend::ImGuiThin_Button(label.into()) }
 }
 
 /// Create a check box with `label`.
 ///
 /// This returns the current state of the check_box.
 pub fn check_box(label: ValidCStr, state: &mut bool) -> bool {
-    unsafe { sys::ImGuiThin_CheckBox(label.into(), state) }
+    unsafe { backend::ImGuiThin_CheckBox(label.into(), state) }
 }
 
 /// Create an input box with `label` of content length `size`.
@@ -55,7 +65,8 @@ pub fn input_text(label: ValidCStr, string: &mut String, size: usize) -> bool {
 
     let buf_size = buf.len();
     let ptr: *mut u8 = buf.as_mut_slice().as_mut_ptr();
-    let changed = unsafe { sys::ImGuiThin_InputText(label.into(), ptr.cast::<c_char>(), buf_size) };
+    let changed =
+        unsafe { backend::ImGuiThin_InputText(label.into(), ptr.cast::<c_char>(), buf_size) };
 
     let new_len = if changed {
         buf.iter()
@@ -92,7 +103,7 @@ pub fn slider_scalar<D: DataType>(label: ValidCStr, value: &mut D, mut min: D, m
     let min: *mut D = &mut min;
     let max: *mut D = &mut max;
     unsafe {
-        sys::ImGuiThin_SliderScalar(
+        backend::ImGuiThin_SliderScalar(
             label.into(),
             D::enum_value(),
             value.cast::<c_void>(),
@@ -102,6 +113,113 @@ pub fn slider_scalar<D: DataType>(label: ValidCStr, value: &mut D, mut min: D, m
     }
 }
 
+/// Create a slider for an [`f32`] with `label` going from `min` to `max`.
+///
+/// Works like [`slider_scalar_clamped`] but reports out-of-range input as a
+/// change.
+/// Returns `true` if changed.
+///
+/// The thin wrapper has no format parameter, so the slider shows the
+/// default format.
+/// With a printf-style `format`, e.g., `"%.0f%%"`, the value is additionally
+/// shown formatted below the slider.
+///
+/// # Panics
+/// Panics if `min > max` or either is NaN, or if `format` does not contain
+/// exactly one floating-point conversion.
+///
+/// # Example
+/// ```no_run
+/// # use mirabel_sys::{cstr, imgui::slider_float};
+/// let mut value = 0.5;
+/// slider_float(cstr("exploration\0"), &mut value, 0.0, 2.0, Some(cstr!("%.2f")));
+/// assert!((0.0..=2.0).contains(&value));
+/// ```
+pub fn slider_float(
+    label: ValidCStr,
+    value: &mut f32,
+    min: f32,
+    max: f32,
+    format: Option<ValidCStr>,
+) -> bool {
+    let changed = slider_scalar_clamped(label, value, min, max).unwrap_or(true);
+    show_formatted(*value, format);
+    changed
+}
+
+/// Create an input for an [`f32`] which is clamped to `min..=max`.
+///
+/// Works like [`input_scalar_clamped`] but reports out-of-range input as a
+/// change.
+/// Returns `true` if changed.
+///
+/// `format` works like for [`slider_float`].
+///
+/// # Panics
+/// Panics if `min > max` or either is NaN, or if `format` does not contain
+/// exactly one floating-point conversion.
+pub fn input_float(
+    label: ValidCStr,
+    value: &mut f32,
+    min: f32,
+    max: f32,
+    format: Option<ValidCStr>,
+) -> bool {
+    let changed = input_scalar_clamped(label, value, min, max).unwrap_or(true);
+    show_formatted(*value, format);
+    changed
+}
+
+/// Show `value` formatted with `format` as a line of text.
+fn show_formatted(value: f32, format: Option<ValidCStr>) {
+    if let Some(format) = format {
+        text(&format_float(format.into(), value));
+    }
+}
+
+/// Format `value` with the printf-style `format`.
+///
+/// # Panics
+/// Panics unless `format` contains exactly one floating-point conversion.
+fn format_float(format: &CStr, value: f32) -> String {
+    assert!(is_float_format(format.to_bytes()), "invalid float format");
+    extern "C" {
+        fn snprintf(s: *mut c_char, n: usize, format: *const c_char, ...) -> c_int;
+    }
+
+    let value = f64::from(value);
+    // The format only consumes a single double, as checked above.
+    let len = unsafe { snprintf(null_mut(), 0, format.as_ptr(), value) };
+    let mut buf = vec![0u8; usize::try_from(len).expect("snprintf failed") + 1];
+    unsafe { snprintf(buf.as_mut_ptr().cast(), buf.len(), format.as_ptr(), value) };
+    buf.pop();
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
+/// Returns `true` if `format` contains exactly one conversion, which takes
+/// a `double`, besides `%%`.
+fn is_float_format(format: &[u8]) -> bool {
+    let mut conversions = 0;
+    let mut rest = format;
+    while let Some(start) = rest.iter().position(|b| *b == b'%') {
+        rest = &rest[start + 1..];
+        if rest.first() == Some(&b'%') {
+            rest = &rest[1..];
+            continue;
+        }
+
+        let spec = rest
+            .iter()
+            .position(|b| !matches!(b, b'-' | b'+' | b' ' | b'#' | b'.' | b'0'..=b'9'));
+        match spec.map(|end| rest[end]) {
+            Some(b'f' | b'F' | b'e' | b'E' | b'g' | b'G' | b'a' | b'A') => conversions += 1,
+            _ => return false,
+        }
+        rest = &rest[spec.unwrap() + 1..];
+    }
+    conversions == 1
+}
+
 /// Create an input for a scalar `D`.
 ///
 /// Displays `value`.
@@ -109,7 +227,77 @@ pub fn slider_scalar<D: DataType>(label: ValidCStr, value: &mut D, mut min: D, m
 /// Returns `true` if changed and stores the updated value in `value`.
 pub fn input_scalar<D: DataType>(label: ValidCStr, value: &mut D) -> bool {
     let value: *mut D = value;
-    unsafe { sys::ImGuiThin_InputScalar(label.into(), D::enum_value(), value.cast::<c_void>()) }
+    unsafe { backend::ImGuiThin_InputScalar(label.into(), D::enum_value(), value.cast::<c_void>()) }
+}
+
+/// Create an input for a scalar `D` which must stay within `min..=max`.
+///
+/// Displays `value`.
+///
+/// Returns whether the value changed.
+/// If the user entered a value outside of the range, `value` is clamped and
+/// [`OutOfRange`] with the entered value is returned.
+///
+/// # Panics
+/// Panics if `min > max`.
+///
+/// # Example
+/// ```no_run
+/// # use mirabel_sys::{cstr, imgui::*};
+/// let mut size = 9u8;
+/// if let Err(OutOfRange { input, .. }) = input_scalar_clamped(cstr!("size"), &mut size, 5, 19) {
+///     text(&format!("Board size {input} is not supported."));
+/// }
+/// ```
+pub fn input_scalar_clamped<D: DataType + PartialOrd + Copy>(
+    label: ValidCStr,
+    value: &mut D,
+    min: D,
+    max: D,
+) -> Result<bool, OutOfRange<D>> {
+    assert!(min <= max, "invalid input range");
+    let changed = input_scalar(label, value);
+    validate(changed, value, min, max)
+}
+
+/// Same as [`input_scalar_clamped`] but creates a slider like
+/// [`slider_scalar`].
+///
+/// _ImGui_ allows entering arbitrary values by CTRL+clicking the slider.
+pub fn slider_scalar_clamped<D: DataType + PartialOrd + Copy>(
+    label: ValidCStr,
+    value: &mut D,
+    min: D,
+    max: D,
+) -> Result<bool, OutOfRange<D>> {
+    assert!(min <= max, "invalid slider range");
+    let changed = slider_scalar(label, value, min, max);
+    validate(changed, value, min, max)
+}
+
+/// User input outside of the allowed range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutOfRange<D> {
+    /// The rejected value.
+    pub input: D,
+    pub min: D,
+    pub max: D,
+}
+
+fn validate<D: PartialOrd + Copy>(
+    changed: bool,
+    value: &mut D,
+    min: D,
+    max: D,
+) -> Result<bool, OutOfRange<D>> {
+    let input = *value;
+    // NaN fails both comparisons and is also rejected.
+    if !changed || (min <= input && input <= max) {
+        return Ok(changed);
+    }
+
+    *value = if input > max { max } else { min };
+    Err(OutOfRange { input, min, max })
 }
 
 /// Disable all UI elements between [`begin_disabled`] and [`end_disabled`].
@@ -118,7 +306,7 @@ pub fn input_scalar<D: DataType>(label: ValidCStr, value: &mut D) -> bool {
 /// Must always be matched with an [`end_disabled`].
 pub fn begin_disabled(disable: bool) {
     unsafe {
-        sys::ImGuiThin_BeginDisabled(disable);
+        backend::ImGuiThin_BeginDisabled(disable);
     }
 }
 
@@ -128,7 +316,7 @@ pub fn begin_disabled(disable: bool) {
 /// Aborts if called without matching [`begin_disabled()`].
 pub fn end_disabled() {
     unsafe {
-        sys::ImGuiThin_EndDisabled();
+        backend::ImGuiThin_EndDisabled();
     }
 }
 
@@ -161,3 +349,100 @@ data_type!(sys::IMGUITHIN_DATATYPE_E_IMGUITHIN_DATATYPE_S64 => i64);
 data_type!(sys::IMGUITHIN_DATATYPE_E_IMGUITHIN_DATATYPE_U64 => u64);
 data_type!(sys::IMGUITHIN_DATATYPE_E_IMGUITHIN_DATATYPE_FLOAT => f32);
 data_type!(sys::IMGUITHIN_DATATYPE_E_IMGUITHIN_DATATYPE_DOUBLE => f64);
+
+/// Runtime options which can be displayed with an [`OptionsUi`].
+pub trait RuntimeOptions {
+    /// Create one widget per field.
+    fn widgets(&mut self, ui: &mut OptionsUi<'_>);
+}
+
+/// Helper for displaying [`RuntimeOptions`], e.g., in
+/// `runtime_opts_display`.
+///
+/// This records the labels of all changed fields.
+///
+/// # Example
+/// ```no_run
+/// # use mirabel_sys::{cstr, imgui::*};
+/// struct Options {
+///     hints: bool,
+///     depth: u8,
+///     name: String,
+/// }
+///
+/// impl RuntimeOptions for Options {
+///     fn widgets(&mut self, ui: &mut OptionsUi<'_>) {
+///         ui.check_box(cstr!("hints"), &mut self.hints)
+///             .slider(cstr!("depth"), &mut self.depth, 1, 20)
+///             .input_text(cstr!("name"), &mut self.name, 32);
+///     }
+/// }
+///
+/// let mut options = Options { hints: false, depth: 4, name: String::new() };
+/// let ui = OptionsUi::display(&mut options);
+/// if ui.is_changed("depth") {
+///     // Restart the search.
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct OptionsUi<'l> {
+    changed: Vec<ValidCStr<'l>>,
+}
+
+impl<'l> OptionsUi<'l> {
+    /// Display all widgets of `options`.
+    pub fn display(options: &mut impl RuntimeOptions) -> Self {
+        let mut ui = Self::default();
+        options.widgets(&mut ui);
+        ui
+    }
+
+    /// See [`check_box`].
+    pub fn check_box(&mut self, label: ValidCStr<'l>, value: &mut bool) -> &mut Self {
+        self.record(label, check_box(label, value))
+    }
+
+    /// See [`slider_scalar`].
+    pub fn slider<D: DataType>(
+        &mut self,
+        label: ValidCStr<'l>,
+        value: &mut D,
+        min: D,
+        max: D,
+    ) -> &mut Self {
+        self.record(label, slider_scalar(label, value, min, max))
+    }
+
+    /// See [`input_text`].
+    pub fn input_text(
+        &mut self,
+        label: ValidCStr<'l>,
+        value: &mut String,
+        size: usize,
+    ) -> &mut Self {
+        self.record(label, input_text(label, value, size))
+    }
+
+    /// Labels of the fields changed in this frame.
+    #[inline]
+    pub fn changed(&self) -> &[ValidCStr<'l>] {
+        &self.changed
+    }
+
+    #[inline]
+    pub fn any_changed(&self) -> bool {
+        !self.changed.is_empty()
+    }
+
+    /// Returns `true` if the field with `label` changed in this frame.
+    pub fn is_changed(&self, label: &str) -> bool {
+        self.changed.iter().any(|l| Into::<&str>::into(*l) == label)
+    }
+
+    fn record(&mut self, label: ValidCStr<'l>, changed: bool) -> &mut Self {
+        if changed {
+            self.changed.push(label);
+        }
+        self
+    }
+}
@@ -0,0 +1,306 @@
+//! Recording stand-in for the _ImGui_ functions of _mirabel_.
+//!
+//! With the `test-stubs` feature, all widgets of [`imgui`](super) are
+//! recorded into a per-thread log instead of being drawn, so UI code can be
+//! unit-tested without _mirabel_.
+//! Widgets report no change unless a response is queued with [`respond`].
+//!
+//! # Example
+//! ```
+//! # use mirabel_sys::{cstr, imgui::{self, stub::*}};
+//! respond("hints", Value::Bool(true));
+//! let mut hints = false;
+//! assert!(imgui::check_box(cstr!("hints"), &mut hints));
+//! imgui::text("done");
+//!
+//! assert!(hints);
+//! assert_eq!(
+//!     vec![
+//!         Call::CheckBox { label: "hints".into(), value: true },
+//!         Call::Text("done".into()),
+//!     ],
+//!     take_log()
+//! );
+//! ```
+
+use std::{
+    cell::RefCell,
+    ffi::{c_char, c_void, CStr},
+    slice,
+};
+
+use crate::sys::{
+    IMGUITHIN_DATATYPE, IMGUITHIN_DATATYPE_E_IMGUITHIN_DATATYPE_DOUBLE as DOUBLE,
+    IMGUITHIN_DATATYPE_E_IMGUITHIN_DATATYPE_FLOAT as FLOAT,
+    IMGUITHIN_DATATYPE_E_IMGUITHIN_DATATYPE_S16 as S16,
+    IMGUITHIN_DATATYPE_E_IMGUITHIN_DATATYPE_S32 as S32,
+    IMGUITHIN_DATATYPE_E_IMGUITHIN_DATATYPE_S64 as S64,
+    IMGUITHIN_DATATYPE_E_IMGUITHIN_DATATYPE_S8 as S8,
+    IMGUITHIN_DATATYPE_E_IMGUITHIN_DATATYPE_U16 as U16,
+    IMGUITHIN_DATATYPE_E_IMGUITHIN_DATATYPE_U32 as U32,
+    IMGUITHIN_DATATYPE_E_IMGUITHIN_DATATYPE_U64 as U64,
+    IMGUITHIN_DATATYPE_E_IMGUITHIN_DATATYPE_U8 as U8,
+};
+
+/// A recorded widget call with the value after the call.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Call {
+    Text(String),
+    Button {
+        label: String,
+    },
+    CheckBox {
+        label: String,
+        value: bool,
+    },
+    InputText {
+        label: String,
+        value: String,
+    },
+    SliderScalar {
+        label: String,
+        value: Value,
+        min: Value,
+        max: Value,
+    },
+    InputScalar {
+        label: String,
+        value: Value,
+    },
+    BeginDisabled(bool),
+    EndDisabled,
+}
+
+/// Value of a widget or user input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// Check box state or button press.
+    Bool(bool),
+    /// Signed integer scalar.
+    Int(i64),
+    /// Unsigned integer scalar.
+    UInt(u64),
+    Float(f64),
+    Text(String),
+}
+
+#[derive(Default)]
+struct State {
+    log: Vec<Call>,
+    responses: Vec<(String, Value)>,
+}
+
+thread_local! {
+    static STATE: RefCell<State> = const {
+        RefCell::new(State {
+            log: Vec::new(),
+            responses: Vec::new(),
+        })
+    };
+}
+
+/// Take all calls recorded on this thread so far.
+pub fn take_log() -> Vec<Call> {
+    STATE.with(|state| std::mem::take(&mut state.borrow_mut().log))
+}
+
+/// Let the next widget with `label` on this thread report `value` as user
+/// input.
+///
+/// Buttons are pressed by [`Value::Bool`]`(true)`.
+/// Scalars are converted with `as` casts.
+///
+/// # Panics
+/// The widget panics if the type of `value` does not fit.
+pub fn respond(label: &str, value: Value) {
+    STATE.with(|state| {
+        state
+            .borrow_mut()
+            .responses
+            .push((label.to_string(), value))
+    });
+}
+
+/// Forget all recorded calls and queued responses on this thread.
+pub fn reset() {
+    STATE.with(|state| *state.borrow_mut() = State::default());
+}
+
+fn record(call: Call) {
+    STATE.with(|state| state.borrow_mut().log.push(call));
+}
+
+fn take_response(label: &str) -> Option<Value> {
+    STATE.with(|state| {
+        let responses = &mut state.borrow_mut().responses;
+        let index = responses.iter().position(|(l, _)| l == label)?;
+        Some(responses.remove(index).1)
+    })
+}
+
+unsafe fn label(label: *const c_char) -> String {
+    CStr::from_ptr(label).to_string_lossy().into_owned()
+}
+
+macro_rules! scalar {
+    ($data_type:expr, $ptr:expr, $value:ident => $map:expr) => {
+        match $data_type {
+            S8 => scalar!(@ i8, $ptr, $value => $map),
+            U8 => scalar!(@ u8, $ptr, $value => $map),
+            S16 => scalar!(@ i16, $ptr, $value => $map),
+            U16 => scalar!(@ u16, $ptr, $value => $map),
+            S32 => scalar!(@ i32, $ptr, $value => $map),
+            U32 => scalar!(@ u32, $ptr, $value => $map),
+            S64 => scalar!(@ i64, $ptr, $value => $map),
+            U64 => scalar!(@ u64, $ptr, $value => $map),
+            FLOAT => scalar!(@ f32, $ptr, $value => $map),
+            DOUBLE => scalar!(@ f64, $ptr, $value => $map),
+            _ => panic!("unknown data type {}", $data_type),
+        }
+    };
+    (@ $type:ty, $ptr:expr, $value:ident => $map:expr) => {{
+        let $value = &mut *$ptr.cast::<$type>();
+        $map
+    }};
+}
+
+unsafe fn read_scalar(data_type: IMGUITHIN_DATATYPE, ptr: *mut c_void) -> Value {
+    scalar!(data_type, ptr, value => ToValue::to_value(*value))
+}
+
+unsafe fn write_scalar(data_type: IMGUITHIN_DATATYPE, ptr: *mut c_void, input: &Value) {
+    scalar!(data_type, ptr, value => *value = match *input {
+        Value::Int(i) => i as _,
+        Value::UInt(u) => u as _,
+        Value::Float(f) => f as _,
+        ref input => panic!("{input:?} is no scalar"),
+    })
+}
+
+trait ToValue {
+    fn to_value(self) -> Value;
+}
+
+macro_rules! to_value {
+    ($variant:ident($target:ty) => $($type:ty),*) => {
+        $(impl ToValue for $type {
+            fn to_value(self) -> Value {
+                Value::$variant(self as $target)
+            }
+        })*
+    };
+}
+
+to_value!(Int(i64) => i8, i16, i32, i64);
+to_value!(UInt(u64) => u8, u16, u32, u64);
+to_value!(Float(f64) => f32, f64);
+
+/// Replacements for the `ImGuiThin_*` functions with identical signatures.
+#[allow(non_snake_case)]
+pub(super) mod backend {
+    use super::*;
+
+    pub unsafe fn ImGuiThin_TextUnformatted(text: *const c_char, text_end: *const c_char) {
+        let len = text_end.offset_from(text).try_into().unwrap();
+        let text = slice::from_raw_parts(text.cast::<u8>(), len);
+        record(Call::Text(String::from_utf8_lossy(text).into_owned()));
+    }
+
+    pub unsafe fn ImGuiThin_Button(label: *const c_char) -> bool {
+        let label = super::label(label);
+        let pressed = match take_response(&label) {
+            Some(Value::Bool(pressed)) => pressed,
+            Some(input) => panic!("{input:?} is no button press"),
+            None => false,
+        };
+        record(Call::Button { label });
+        pressed
+    }
+
+    pub unsafe fn ImGuiThin_CheckBox(label: *const c_char, v: *mut bool) -> bool {
+        let label = super::label(label);
+        let changed = match take_response(&label) {
+            Some(Value::Bool(value)) => {
+                *v = value;
+                true
+            }
+            Some(input) => panic!("{input:?} is no check box state"),
+            None => false,
+        };
+        record(Call::CheckBox { label, value: *v });
+        changed
+    }
+
+    pub unsafe fn ImGuiThin_InputText(
+        label: *const c_char,
+        buf: *mut c_char,
+        buf_size: usize,
+    ) -> bool {
+        let label = super::label(label);
+        let buf = slice::from_raw_parts_mut(buf.cast::<u8>(), buf_size);
+        let changed = match take_response(&label) {
+            Some(Value::Text(mut text)) => {
+                let mut len = text.len().min(buf_size - 1);
+                while !text.is_char_boundary(len) {
+                    len -= 1;
+                }
+                text.truncate(len);
+                buf[..len].copy_from_slice(text.as_bytes());
+                buf[len] = 0;
+                true
+            }
+            Some(input) => panic!("{input:?} is no text"),
+            None => false,
+        };
+        let value = CStr::from_bytes_until_nul(buf).expect("buffer not NUL-terminated");
+        let value = value.to_string_lossy().into_owned();
+        record(Call::InputText { label, value });
+        changed
+    }
+
+    pub unsafe fn ImGuiThin_SliderScalar(
+        label: *const c_char,
+        data_type: IMGUITHIN_DATATYPE,
+        p_data: *mut c_void,
+        p_min: *mut c_void,
+        p_max: *mut c_void,
+    ) -> bool {
+        let label = super::label(label);
+        let response = take_response(&label);
+        if let Some(input) = &response {
+            write_scalar(data_type, p_data, input);
+        }
+        record(Call::SliderScalar {
+            label,
+            value: read_scalar(data_type, p_data),
+            min: read_scalar(data_type, p_min),
+            max: read_scalar(data_type, p_max),
+        });
+        response.is_some()
+    }
+
+    pub unsafe fn ImGuiThin_InputScalar(
+        label: *const c_char,
+        data_type: IMGUITHIN_DATATYPE,
+        p_data: *mut c_void,
+    ) -> bool {
+        let label = super::label(label);
+        let response = take_response(&label);
+        if let Some(input) = &response {
+            write_scalar(data_type, p_data, input);
+        }
+        record(Call::InputScalar {
+            label,
+            value: read_scalar(data_type, p_data),
+        });
+        response.is_some()
+    }
+
+    pub unsafe fn ImGuiThin_BeginDisabled(disabled: bool) {
+        record(Call::BeginDisabled(disabled));
+    }
+
+    pub unsafe fn ImGuiThin_EndDisabled() {
+        record(Call::EndDisabled);
+    }
+}
@@ -0,0 +1,143 @@
+//! Wrapper for the SDL input events passed to frontends.
+
+use crate::sys::{
+    SDL_Event, SDL_EventType_SDL_KEYDOWN as KEYDOWN, SDL_EventType_SDL_KEYUP as KEYUP,
+    SDL_EventType_SDL_MOUSEBUTTONDOWN as MOUSEBUTTONDOWN,
+    SDL_EventType_SDL_MOUSEBUTTONUP as MOUSEBUTTONUP, SDL_EventType_SDL_MOUSEMOTION as MOUSEMOTION,
+    SDL_EventType_SDL_MOUSEWHEEL as MOUSEWHEEL, SDL_EventType_SDL_WINDOWEVENT as WINDOWEVENT,
+    SDL_KeyboardEvent, SDL_Keycode, SDL_MouseButtonEvent, SDL_MouseMotionEvent,
+    SDL_MouseWheelDirection_SDL_MOUSEWHEEL_FLIPPED, SDL_MouseWheelEvent, SDL_Scancode,
+    SDL_WindowEventID_SDL_WINDOWEVENT_RESIZED as RESIZED,
+    SDL_WindowEventID_SDL_WINDOWEVENT_SIZE_CHANGED as SIZE_CHANGED,
+};
+
+/// SDL input event converted to a Rust enum.
+///
+/// Coordinates are in window pixels as reported by SDL.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum InputEvent {
+    MouseButtonDown(MouseButton),
+    MouseButtonUp(MouseButton),
+    MouseMotion(MouseMotion),
+    KeyDown(Key),
+    KeyUp(Key),
+    Wheel(Wheel),
+    WindowResize { width: i32, height: i32 },
+    Unknown,
+}
+
+impl InputEvent {
+    /// Create a new [`InputEvent`] from an [`SDL_Event`].
+    pub fn new(event: &SDL_Event) -> Self {
+        // All SDL event structs consist of plain integers and start with the
+        // type, so reading any variant is defined.
+        unsafe {
+            match event.type_ {
+                MOUSEBUTTONDOWN => Self::MouseButtonDown(MouseButton::new(&event.button)),
+                MOUSEBUTTONUP => Self::MouseButtonUp(MouseButton::new(&event.button)),
+                MOUSEMOTION => Self::MouseMotion(MouseMotion::new(&event.motion)),
+                KEYDOWN => Self::KeyDown(Key::new(&event.key)),
+                KEYUP => Self::KeyUp(Key::new(&event.key)),
+                MOUSEWHEEL => Self::Wheel(Wheel::new(&event.wheel)),
+                WINDOWEVENT => match u32::from(event.window.event) {
+                    RESIZED | SIZE_CHANGED => Self::WindowResize {
+                        width: event.window.data1,
+                        height: event.window.data2,
+                    },
+                    _ => Self::Unknown,
+                },
+                _ => Self::Unknown,
+            }
+        }
+    }
+}
+
+impl From<&SDL_Event> for InputEvent {
+    #[inline]
+    fn from(event: &SDL_Event) -> Self {
+        Self::new(event)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MouseButton {
+    /// SDL button index (`SDL_BUTTON_LEFT` is 1).
+    pub button: u8,
+    pub clicks: u8,
+    pub x: i32,
+    pub y: i32,
+}
+
+impl MouseButton {
+    fn new(event: &SDL_MouseButtonEvent) -> Self {
+        Self {
+            button: event.button,
+            clicks: event.clicks,
+            x: event.x,
+            y: event.y,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MouseMotion {
+    /// Bitmask of the pressed mouse buttons.
+    pub state: u32,
+    pub x: i32,
+    pub y: i32,
+    pub x_rel: i32,
+    pub y_rel: i32,
+}
+
+impl MouseMotion {
+    fn new(event: &SDL_MouseMotionEvent) -> Self {
+        Self {
+            state: event.state,
+            x: event.x,
+            y: event.y,
+            x_rel: event.xrel,
+            y_rel: event.yrel,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Key {
+    pub keycode: SDL_Keycode,
+    pub scancode: SDL_Scancode,
+    /// Bitmask of the active `SDL_Keymod` modifiers.
+    pub modifiers: u16,
+    pub repeat: bool,
+}
+
+impl Key {
+    fn new(event: &SDL_KeyboardEvent) -> Self {
+        Self {
+            keycode: event.keysym.sym,
+            scancode: event.keysym.scancode,
+            modifiers: event.keysym.mod_,
+            repeat: event.repeat != 0,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Wheel {
+    /// Horizontal scroll amount, positive to the right.
+    pub x: i32,
+    /// Vertical scroll amount, positive away from the user.
+    pub y: i32,
+}
+
+impl Wheel {
+    /// Normalizes the scroll direction if SDL reports it as flipped.
+    fn new(event: &SDL_MouseWheelEvent) -> Self {
+        let (x, y) = if event.direction == SDL_MouseWheelDirection_SDL_MOUSEWHEEL_FLIPPED {
+            (-event.x, -event.y)
+        } else {
+            (event.x, event.y)
+        };
+        Self { x, y }
+    }
+}
@@ -5,6 +5,32 @@
 //! # Features
 //! - `surena`: Generate bindings for _surena_.
 //! - `mirabel`: Generate bindings also for _mirabel_.
+//! - `log`: Provide a [`log`](https://docs.rs/log) backend which logs to
+//!   _mirabel_.
+//! - `arbitrary`: Implement [`Arbitrary`](https://docs.rs/arbitrary) for
+//!   model types like [`game_init::GameInit`], e.g., for fuzzing plugins
+//!   with `testing::GameTester::fuzz`.
+//! - `serde`: Implement [`serde`](https://docs.rs/serde) traits for wrapper
+//!   types like [`game_init::OwnedGameInit`] and [`event::OwnedEventEnum`].
+//! - `loader`: Load _surena_ game plugins from shared libraries with
+//!   [`libloading`](https://docs.rs/libloading).
+//! - `test-stubs`: Record the widgets of [`imgui`] instead of drawing them,
+//!   see `imgui::stub`.
+//!
+//! # Stability
+//! The generated items in [`raw`] (also available as [`sys`]) follow the
+//! _surena_ and _mirabel_ headers and may change with every regeneration of
+//! the bindings.
+//! The safe wrappers in [`api`] only change with a new version of this
+//! crate.
+//! Items marked `#[doc(hidden)]`, e.g., `game_init::SysGameInit` or the
+//! recording `imgui::stub`, are internal or test helpers and exempt from
+//! both.
+//!
+//! The top-level module paths like `mirabel_sys::game` stay available
+//! without deprecation.
+//! Deprecating them in favor of [`api`] is left for a release which removes
+//! them.
 
 #[cfg(feature = "surena")]
 pub mod sys;
@@ -12,22 +38,99 @@ pub mod sys;
 #[cfg(feature = "surena")]
 pub mod error;
 
+#[cfg(feature = "surena")]
+pub mod game;
+
 #[cfg(feature = "surena")]
 pub mod game_init;
 
+#[cfg(feature = "surena")]
+pub mod game_features;
+
+#[cfg(feature = "surena")]
+pub mod player;
+
+#[cfg(feature = "surena")]
+pub mod out_array;
+
+#[cfg(feature = "surena")]
+pub mod replay;
+
+#[cfg(feature = "surena")]
+pub mod testing;
+
+#[cfg(feature = "surena")]
+pub mod version;
+
+#[cfg(feature = "loader")]
+pub mod loader;
+
+#[cfg(feature = "surena")]
+pub mod notation;
+
+#[cfg(feature = "surena")]
+pub mod options;
+
+#[cfg(feature = "surena")]
+pub mod perft;
+
+#[cfg(feature = "surena")]
+pub mod pool;
+
+#[cfg(feature = "mirabel")]
+pub mod display_data;
+
 #[cfg(feature = "mirabel")]
 pub mod event;
 
 #[cfg(feature = "mirabel")]
 pub mod imgui;
 
+#[cfg(feature = "mirabel")]
+pub mod input;
+
 #[cfg(feature = "mirabel")]
 pub mod log;
 
+#[cfg(feature = "mirabel")]
+pub mod mirror;
+
+#[cfg(feature = "mirabel")]
+pub mod window;
+
+pub mod blob;
+
+pub mod random;
+
+pub mod search;
+
 pub mod string;
 
 pub use string::*;
 
+/// Generated bindings, see [`sys`].
+#[cfg(feature = "surena")]
+pub mod raw {
+    pub use crate::sys::*;
+}
+
+/// Safe wrappers around the plugin APIs.
+pub mod api {
+    #[cfg(feature = "surena")]
+    pub use crate::{
+        error, game, game_features, game_init, notation, options, out_array, perft, player, pool,
+        replay, testing, version,
+    };
+
+    #[cfg(feature = "loader")]
+    pub use crate::loader;
+
+    #[cfg(feature = "mirabel")]
+    pub use crate::{display_data, event, imgui, input, log, mirror, window};
+
+    pub use crate::{blob, random, search, string};
+}
+
 /// Simple macro for counting the number of provided arguments.
 ///
 /// # Example
@@ -40,3 +143,21 @@ macro_rules! count {
     () => { 0 };
     ($_e: expr $(, $rest: expr)*) => { 1 + $crate::count!($($rest),*) }
 }
+
+/// Convert `(column, row)` direction pairs into offsets on a board of the
+/// given `width` whose cells are numbered row by row.
+///
+/// This can be evaluated at compile time.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::offsets;
+/// const NEIGHBORS: [isize; 4] = offsets!(8; (1, 0), (-1, 0), (0, 1), (0, -1));
+/// assert_eq!([1, -1, 8, -8], NEIGHBORS);
+/// ```
+#[macro_export]
+macro_rules! offsets {
+    ($width: expr; $(($column: expr, $row: expr)),* $(,)?) => {
+        [$(($column) as isize + ($row) as isize * ($width) as isize),*]
+    };
+}
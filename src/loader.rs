@@ -0,0 +1,110 @@
+//! Loading _surena_ game plugins from shared libraries, e.g., for tests and
+//! tools.
+
+use std::{
+    ffi::OsStr,
+    ptr::{null, null_mut},
+};
+
+use libloading::Library;
+
+use crate::{
+    error::{Error, ErrorCode, Result},
+    game::GameMethodsRef,
+    sys::game_methods,
+    version::SURENA_GAME_API_VERSION,
+};
+
+type GetVersionFn = unsafe extern "C" fn() -> u64;
+type GetMethodsFn = unsafe extern "C" fn(count: *mut u32, methods: *mut *const game_methods);
+
+/// A loaded game plugin, which is unloaded on drop.
+///
+/// # Example
+/// ```no_run
+/// # use mirabel_sys::loader::GamePlugin;
+/// let plugin = unsafe { GamePlugin::load("./libmygame.so") }.unwrap();
+/// for methods in plugin.methods() {
+///     println!("{}.{}.{}", methods.game_name(), methods.variant_name(), methods.impl_name());
+/// }
+/// ```
+pub struct GamePlugin {
+    methods: Vec<*const game_methods>,
+    // Dropped last because `methods` point into the library.
+    _library: Library,
+}
+
+impl GamePlugin {
+    /// Load the plugin at `path` and query its game methods.
+    ///
+    /// Fails if the library cannot be loaded, lacks the plugin functions or
+    /// was built for another [`SURENA_GAME_API_VERSION`].
+    ///
+    /// # Safety
+    /// Loading a library runs its initialization code.
+    /// The plugin functions must follow the _surena_ plugin API.
+    pub unsafe fn load(path: impl AsRef<OsStr>) -> Result<Self> {
+        let library = Library::new(path).map_err(invalid)?;
+
+        let get_version = library
+            .get::<GetVersionFn>(b"plugin_get_game_capi_version\0")
+            .map_err(invalid)?;
+        let version = get_version();
+        if version != SURENA_GAME_API_VERSION {
+            return Err(Error::new_dynamic(
+                ErrorCode::InvalidInput,
+                format!(
+                    "plugin uses game API version {version} instead of {SURENA_GAME_API_VERSION}"
+                ),
+            ));
+        }
+
+        let get_methods = library
+            .get::<GetMethodsFn>(b"plugin_get_game_methods\0")
+            .map_err(invalid)?;
+        let mut count = 0;
+        get_methods(&mut count, null_mut());
+        let capacity = count;
+        let mut methods = vec![null(); capacity.try_into().unwrap()];
+        get_methods(&mut count, methods.as_mut_ptr());
+        if count > capacity {
+            return Err(Error::new_dynamic(
+                ErrorCode::InvalidInput,
+                format!("plugin returned {count} game methods but announced {capacity}"),
+            ));
+        }
+        methods.truncate(count.try_into().unwrap());
+        methods.retain(|m| !m.is_null());
+
+        Ok(Self {
+            methods,
+            _library: library,
+        })
+    }
+
+    /// All game methods provided by the plugin.
+    ///
+    /// Games created from these must be dropped before the plugin.
+    pub fn methods(&self) -> impl Iterator<Item = GameMethodsRef<'_>> + '_ {
+        // Valid until the library is unloaded.
+        self.methods
+            .iter()
+            .filter_map(|&m| unsafe { GameMethodsRef::from_ptr(m) })
+    }
+
+    /// Find methods by their game, variant and implementation names.
+    pub fn find(
+        &self,
+        game: &str,
+        variant: &str,
+        implementation: &str,
+    ) -> Option<GameMethodsRef<'_>> {
+        self.methods().find(|m| {
+            m.game_name() == game && m.variant_name() == variant && m.impl_name() == implementation
+        })
+    }
+}
+
+fn invalid(error: libloading::Error) -> Error {
+    Error::new_dynamic(ErrorCode::InvalidInput, error.to_string())
+}
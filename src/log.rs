@@ -1,6 +1,10 @@
 //! Wrapper for the _mirabel_ log framework.
 
-use std::ffi::c_char;
+use std::{
+    cell::RefCell,
+    ffi::c_char,
+    fmt::{self, Display, Write},
+};
 
 use crate::sys;
 
@@ -8,8 +12,61 @@ use crate::sys;
 ///
 /// To prevent accidental coloring, all log messages start with "+ ".
 /// The wrapper will append a newline for you.
+#[inline]
 pub fn mirabel_log(msg: &str) {
-    let msg = format!("+ {msg}\n");
+    mirabel_log_fmt(format_args!("{msg}"));
+}
+
+/// Same as [`mirabel_log`] but takes [`fmt::Arguments`].
+///
+/// Messages are formatted into a thread-local buffer, so this does not
+/// allocate for each call.
+/// Prefer the [`mirabel_log!`](crate::mirabel_log!) macro.
+pub fn mirabel_log_fmt(args: fmt::Arguments) {
+    // Keep the buffer from growing indefinitely after huge messages.
+    const MAX_KEPT_CAPACITY: usize = 1 << 16;
+    thread_local! {
+        static BUFFER: RefCell<String> = const { RefCell::new(String::new()) };
+    }
+
+    BUFFER.with(|buffer| {
+        // A Display implementation could log by itself.
+        let mut fallback = String::new();
+        let mut borrowed = buffer.try_borrow_mut();
+        let buffer = match &mut borrowed {
+            Ok(b) => &mut **b,
+            Err(_) => &mut fallback,
+        };
+
+        buffer.clear();
+        buffer.push_str("+ ");
+        buffer
+            .write_fmt(args)
+            .expect("formatting log message failed");
+        buffer.push('\n');
+        log_raw(buffer);
+        if buffer.capacity() > MAX_KEPT_CAPACITY {
+            *buffer = String::new();
+        }
+    });
+}
+
+/// Log a message formatted like [`format!`] via [`mirabel_log_fmt`].
+///
+/// # Example
+/// ```no_run
+/// # use mirabel_sys::mirabel_log;
+/// let nodes = 42;
+/// mirabel_log!("searched {nodes} nodes");
+/// ```
+#[macro_export]
+macro_rules! mirabel_log {
+    ($($arg:tt)*) => {
+        $crate::log::mirabel_log_fmt(::std::format_args!($($arg)*))
+    };
+}
+
+fn log_raw(msg: &str) {
     let msg = msg.as_bytes().as_ptr_range();
     let start: *const u8 = msg.start;
     let end: *const u8 = msg.end;
@@ -17,3 +74,94 @@ pub fn mirabel_log(msg: &str) {
         sys::mirabel_log(start.cast::<c_char>(), end.cast::<c_char>());
     }
 }
+
+/// Severity of a log message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        };
+        f.pad(level)
+    }
+}
+
+/// Same as [`mirabel_log`] but tags the message with its `level`.
+///
+/// The message is logged as `+ [level] msg`.
+#[inline]
+pub fn mirabel_log_level(level: LogLevel, msg: &str) {
+    mirabel_log_fmt(format_args!("[{level}] {msg}"));
+}
+
+#[cfg(feature = "log")]
+pub use logger::MirabelLogger;
+
+#[cfg(feature = "log")]
+mod logger {
+    use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+    use super::{mirabel_log_fmt, LogLevel};
+
+    /// [`Log`] implementation which forwards to [`mirabel_log_fmt`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use mirabel_sys::log::MirabelLogger;
+    /// MirabelLogger::init(log::LevelFilter::Info).unwrap();
+    /// log::info!("plugin loaded");
+    /// ```
+    #[derive(Debug, Default)]
+    pub struct MirabelLogger;
+
+    impl MirabelLogger {
+        /// Install the logger globally and set the maximum level.
+        ///
+        /// Fails if another logger was installed before.
+        pub fn init(level: LevelFilter) -> Result<(), SetLoggerError> {
+            static LOGGER: MirabelLogger = MirabelLogger;
+            log::set_logger(&LOGGER)?;
+            log::set_max_level(level);
+            Ok(())
+        }
+    }
+
+    impl Log for MirabelLogger {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            metadata.level() <= log::max_level()
+        }
+
+        fn log(&self, record: &Record) {
+            if self.enabled(record.metadata()) {
+                let level = LogLevel::from(record.level());
+                mirabel_log_fmt(format_args!("[{level}] {}", record.args()));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    impl From<Level> for LogLevel {
+        fn from(level: Level) -> Self {
+            match level {
+                Level::Error => LogLevel::Error,
+                Level::Warn => LogLevel::Warn,
+                Level::Info => LogLevel::Info,
+                Level::Debug => LogLevel::Debug,
+                Level::Trace => LogLevel::Trace,
+            }
+        }
+    }
+}
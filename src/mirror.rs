@@ -0,0 +1,240 @@
+//! Local copy of the host's game for frontends.
+
+use crate::{
+    error::Result,
+    event::{
+        EventAny, EventEnum, EventGameMove, OwnedEventEnum, OwnedEventGameLoadMethods,
+        OwnedEventGameState,
+    },
+    game::Game,
+    player::PlayerId,
+    sys::move_code,
+};
+
+/// Game which mirrors the host's game by consuming events.
+///
+/// Frontends usually keep their own [`Game`] in sync with the host.
+/// This loads, updates and unloads it from [`EventEnum`]s and caches the
+/// state string and the available moves for rendering.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{event::EventAny, mirror::MirrorGame};
+/// fn process_event(mirror: &mut MirrorGame, event: &EventAny) {
+///     if let Ok(true) = mirror.handle(&event.to_rust()) {
+///         // Redraw using mirror.state() and mirror.moves(...).
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct MirrorGame {
+    game: Option<Game>,
+    state: String,
+    moves: Vec<(PlayerId, Vec<move_code>)>,
+}
+
+impl MirrorGame {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply `event` to the mirrored game.
+    ///
+    /// Returns `true` if the game changed.
+    /// If updating the game fails, it is unloaded and the error is returned.
+    pub fn handle(&mut self, event: &EventEnum) -> Result<bool> {
+        let result = self.apply(event);
+        if result.is_err() {
+            self.unload();
+        }
+        result
+    }
+
+    fn apply(&mut self, event: &EventEnum) -> Result<bool> {
+        match event {
+            EventEnum::GameLoadMethods(e) => {
+                self.unload();
+                // The host keeps the methods valid until the game is unloaded.
+                self.game = Some(unsafe { e.methods.create(e.init_info.clone())? });
+            }
+            EventEnum::GameUnload(_) => {
+                let changed = self.game.is_some();
+                self.unload();
+                return Ok(changed);
+            }
+            EventEnum::GameState(e) => match &mut self.game {
+                Some(game) => game.import_state(e.state.map(Into::into))?,
+                None => return Ok(false),
+            },
+            EventEnum::GameMove(e) => match &mut self.game {
+                Some(game) => game.make_move(e.player, e.code)?,
+                None => return Ok(false),
+            },
+            EventEnum::ChatMsg(_) | EventEnum::Unknown => return Ok(false),
+        }
+
+        self.refresh()?;
+        Ok(true)
+    }
+
+    /// Update the cached state and moves, e.g., after using
+    /// [`MirrorGame::game_mut`].
+    pub fn refresh(&mut self) -> Result<()> {
+        self.state.clear();
+        self.moves.clear();
+        let Some(game) = &mut self.game else {
+            return Ok(());
+        };
+
+        game.export_state(&mut self.state)?;
+        for player in game.players_to_move()? {
+            let moves = game.concrete_moves(player)?;
+            self.moves.push((player, moves));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn is_loaded(&self) -> bool {
+        self.game.is_some()
+    }
+
+    #[inline]
+    pub fn game(&self) -> Option<&Game> {
+        self.game.as_ref()
+    }
+
+    /// Mutable access to the game.
+    ///
+    /// Call [`MirrorGame::refresh`] after changing it.
+    #[inline]
+    pub fn game_mut(&mut self) -> Option<&mut Game> {
+        self.game.as_mut()
+    }
+
+    /// Current state string or an empty string if no game is loaded.
+    #[inline]
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    /// Players which currently have to move.
+    pub fn players_to_move(&self) -> impl Iterator<Item = PlayerId> + '_ {
+        self.moves.iter().map(|(player, _)| *player)
+    }
+
+    /// Concrete moves of `player` or an empty slice if not to move.
+    pub fn moves(&self, player: PlayerId) -> &[move_code] {
+        self.moves
+            .iter()
+            .find(|(p, _)| *p == player)
+            .map_or(&[], |(_, moves)| moves)
+    }
+
+    fn unload(&mut self) {
+        self.game = None;
+        self.state.clear();
+        self.moves.clear();
+    }
+}
+
+/// Load status and latest state of the host's game without a local copy.
+///
+/// Tracks the load, state and move events since the last unload, so that
+/// they can be re-sent, e.g., after a frontend was recreated.
+/// Use [`MirrorGame`] if the moves should also be applied.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{event::EventAny, mirror::GameSession};
+/// fn process_event(session: &mut GameSession, event: &EventAny) {
+///     if session.on_event(&event.to_rust()) && session.is_loaded() {
+///         println!("state: {:?}", session.current_state());
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GameSession {
+    load: Option<OwnedEventGameLoadMethods>,
+    state: Option<OwnedEventGameState>,
+    moves: Vec<EventGameMove>,
+}
+
+impl GameSession {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the session from `event`.
+    ///
+    /// Only the stored events are copied.
+    /// Returns `true` if the session changed.
+    pub fn on_event(&mut self, event: &EventEnum) -> bool {
+        match event {
+            EventEnum::GameLoadMethods(e) => {
+                self.unload();
+                self.load = Some(e.to_owned());
+            }
+            EventEnum::GameUnload(_) => {
+                let changed = self.is_loaded();
+                self.unload();
+                return changed;
+            }
+            EventEnum::GameState(e) if self.is_loaded() => {
+                self.state = Some(e.to_owned());
+                self.moves.clear();
+            }
+            EventEnum::GameMove(e) if self.is_loaded() => self.moves.push(*e),
+            _ => return false,
+        }
+        true
+    }
+
+    #[inline]
+    pub fn is_loaded(&self) -> bool {
+        self.load.is_some()
+    }
+
+    /// State string of the latest state event.
+    ///
+    /// Moves made since then are available from
+    /// [`GameSession::moves_since_state`].
+    /// Returns [`None`] if no game is loaded or no state has been received.
+    pub fn current_state(&self) -> Option<&str> {
+        self.state.as_ref()?.state.as_deref()
+    }
+
+    /// Move events since the latest state event.
+    #[inline]
+    pub fn moves_since_state(&self) -> &[EventGameMove] {
+        &self.moves
+    }
+
+    /// Events which restore the current game from scratch.
+    ///
+    /// Empty if no game is loaded.
+    /// Events which cannot be recreated, e.g., because of NULL methods, are
+    /// skipped.
+    pub fn restore_events(&self) -> Vec<EventAny> {
+        let Some(load) = &self.load else {
+            return vec![];
+        };
+        let load = OwnedEventEnum::GameLoadMethods(load.clone());
+        let state = self.state.clone().map(OwnedEventEnum::GameState);
+        let moves = self.moves.iter().copied().map(OwnedEventEnum::GameMove);
+        [load]
+            .into_iter()
+            .chain(state)
+            .chain(moves)
+            .filter_map(|e| e.to_event())
+            .collect()
+    }
+
+    fn unload(&mut self) {
+        self.load = None;
+        self.state = None;
+        self.moves.clear();
+    }
+}
@@ -0,0 +1,149 @@
+//! Human-friendly notations for [`move_code`]s.
+//!
+//! These are meant for games without a custom move string, e.g., for logging.
+//! All notations assume that board cells are numbered row by row.
+
+use std::fmt::{self, Display};
+
+use crate::sys::{move_code, MOVE_NONE};
+
+/// A way of formatting and parsing [`move_code`]s.
+pub trait Notation {
+    /// Write `code` to `f`.
+    ///
+    /// Only called for codes other than [`MOVE_NONE`].
+    fn fmt(&self, code: move_code, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Parse a string written by [`Notation::fmt`].
+    fn parse(&self, string: &str) -> Option<move_code>;
+
+    /// Returns a [`Display`] adapter for `code`.
+    ///
+    /// [`MOVE_NONE`] is displayed as `none`.
+    #[inline]
+    fn display(&self, code: move_code) -> MoveDisplay<'_, Self> {
+        MoveDisplay {
+            notation: self,
+            code,
+        }
+    }
+}
+
+/// [`Display`] adapter returned by [`Notation::display`].
+pub struct MoveDisplay<'l, N: ?Sized> {
+    notation: &'l N,
+    code: move_code,
+}
+
+impl<'l, N: Notation + ?Sized> Display for MoveDisplay<'l, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.code == MOVE_NONE {
+            write!(f, "none")
+        } else {
+            self.notation.fmt(self.code, f)
+        }
+    }
+}
+
+/// Plain decimal move codes.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::notation::*;
+/// assert_eq!("42", Numeric.display(42).to_string());
+/// assert_eq!(Some(42), Numeric.parse("42"));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Numeric;
+
+impl Notation for Numeric {
+    fn fmt(&self, code: move_code, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{code}")
+    }
+
+    fn parse(&self, string: &str) -> Option<move_code> {
+        string.parse().ok().filter(|c| *c != MOVE_NONE)
+    }
+}
+
+/// Algebraic notation for square grids of up to 26 columns.
+///
+/// Columns are letters starting at `a` and rows are numbers starting at 1.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::notation::*;
+/// let notation = Algebraic::new(8);
+/// assert_eq!("e2", notation.display(12).to_string());
+/// assert_eq!(Some(12), notation.parse("e2"));
+/// assert_eq!(None, notation.parse("i1"));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Algebraic {
+    width: u8,
+}
+
+impl Algebraic {
+    /// # Panics
+    /// Panics if `width` is not in `1..=26`.
+    pub fn new(width: u8) -> Self {
+        assert!((1..=26).contains(&width), "unsupported board width");
+        Self { width }
+    }
+}
+
+impl Notation for Algebraic {
+    fn fmt(&self, code: move_code, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = move_code::from(self.width);
+        let column = char::from(b'a' + (code % width) as u8);
+        write!(f, "{column}{}", code / width + 1)
+    }
+
+    fn parse(&self, string: &str) -> Option<move_code> {
+        let column = *string.as_bytes().first()?;
+        let column = column.checked_sub(b'a').filter(|c| *c < self.width)?;
+        let row: move_code = string[1..].parse().ok()?;
+        let code = row
+            .checked_sub(1)?
+            .checked_mul(self.width.into())?
+            .checked_add(column.into())?;
+        (code != MOVE_NONE).then_some(code)
+    }
+}
+
+/// Axial `q,r` coordinates for hexagonal boards stored as rhombi.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::notation::*;
+/// let notation = Axial::new(11);
+/// assert_eq!("3,2", notation.display(25).to_string());
+/// assert_eq!(Some(25), notation.parse("3,2"));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Axial {
+    width: move_code,
+}
+
+impl Axial {
+    /// # Panics
+    /// Panics if `width` is zero.
+    pub fn new(width: move_code) -> Self {
+        assert_ne!(0, width, "unsupported board width");
+        Self { width }
+    }
+}
+
+impl Notation for Axial {
+    fn fmt(&self, code: move_code, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", code % self.width, code / self.width)
+    }
+
+    fn parse(&self, string: &str) -> Option<move_code> {
+        let (q, r) = string.split_once(',')?;
+        let q: move_code = q.trim().parse().ok().filter(|q| *q < self.width)?;
+        let r: move_code = r.trim().parse().ok()?;
+        let code = r.checked_mul(self.width)?.checked_add(q)?;
+        (code != MOVE_NONE).then_some(code)
+    }
+}
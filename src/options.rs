@@ -0,0 +1,179 @@
+//! Parser and serializer for `key=value` game option strings.
+//!
+//! Options are separated by whitespace, e.g., `size=9 komi=6.5`.
+//! All errors are reported as [`ErrorCode::InvalidOptions`].
+
+use std::{
+    fmt::{Display, Write},
+    str::FromStr,
+};
+
+use crate::error::{Error, ErrorCode, Result};
+
+/// Parsed option string whose values are taken one by one.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::options::Options;
+/// let mut options = Options::parse("size=9 komi=6.5").unwrap();
+/// assert_eq!(9u8, options.get_or("size", 19).unwrap());
+/// assert_eq!(Some(6.5f32), options.get("komi").unwrap());
+/// assert_eq!(false, options.get_or("swap", false).unwrap());
+/// options.finish().unwrap();
+///
+/// assert!(Options::parse("size").is_err());
+/// assert!(Options::parse("size=9 size=13").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Options<'s> {
+    pairs: Vec<(&'s str, &'s str)>,
+}
+
+impl<'s> Options<'s> {
+    /// Split `string` into `key=value` pairs.
+    ///
+    /// Fails on tokens without `=`, empty keys and duplicate keys.
+    pub fn parse(string: &'s str) -> Result<Self> {
+        let mut pairs: Vec<(&str, &str)> = vec![];
+        for token in string.split_whitespace() {
+            let Some((key, value)) = token.split_once('=').filter(|(k, _)| !k.is_empty()) else {
+                return Err(invalid(format!("expected key=value but got {token:?}")));
+            };
+            if pairs.iter().any(|(k, _)| *k == key) {
+                return Err(invalid(format!("duplicate option {key:?}")));
+            }
+            pairs.push((key, value));
+        }
+
+        Ok(Self { pairs })
+    }
+
+    /// Remove and parse the value of `key` if present.
+    pub fn get<T: FromStr>(&mut self, key: &str) -> Result<Option<T>> {
+        let Some(index) = self.pairs.iter().position(|(k, _)| *k == key) else {
+            return Ok(None);
+        };
+        let (_, value) = self.pairs.swap_remove(index);
+        match value.parse() {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => Err(invalid(format!("invalid value {value:?} for {key:?}"))),
+        }
+    }
+
+    /// Same as [`Options::get`] but returns `default` if `key` is missing.
+    #[inline]
+    pub fn get_or<T: FromStr>(&mut self, key: &str, default: T) -> Result<T> {
+        Ok(self.get(key)?.unwrap_or(default))
+    }
+
+    /// Fail if any option has not been taken, i.e., is unknown.
+    pub fn finish(self) -> Result<()> {
+        match self.pairs.first() {
+            Some((key, _)) => Err(invalid(format!("unknown option {key:?}"))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Builder for option strings readable by [`Options`].
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::options::OptionsWriter;
+/// let mut writer = OptionsWriter::new();
+/// writer.push("size", 9).push("komi", 6.5);
+/// assert_eq!("size=9 komi=6.5", writer.finish());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OptionsWriter {
+    string: String,
+}
+
+impl OptionsWriter {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an option.
+    ///
+    /// # Panics
+    /// Panics in debug builds if `key` or `value` would not be parsed back
+    /// correctly.
+    pub fn push(&mut self, key: &str, value: impl Display) -> &mut Self {
+        if !self.string.is_empty() {
+            self.string.push(' ');
+        }
+        let start = self.string.len();
+        self.string.push_str(key);
+        self.string.push('=');
+        write!(self.string, "{value}").unwrap();
+
+        debug_assert!(
+            !key.is_empty()
+                && !key.contains('=')
+                && !self.string[start..].contains(char::is_whitespace)
+                && !self.string[start..].contains('\0'),
+            "option {:?} cannot be parsed back",
+            &self.string[start..]
+        );
+        self
+    }
+
+    #[inline]
+    pub fn finish(self) -> String {
+        self.string
+    }
+}
+
+/// Game options which are stored as an option string.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{error::Result, options::*};
+/// #[derive(Debug, PartialEq)]
+/// struct BoardOptions {
+///     size: u8,
+/// }
+///
+/// impl GameOptions for BoardOptions {
+///     fn from_options(options: &mut Options) -> Result<Self> {
+///         Ok(Self { size: options.get_or("size", 19)? })
+///     }
+///
+///     fn write_options(&self, writer: &mut OptionsWriter) {
+///         writer.push("size", self.size);
+///     }
+/// }
+///
+/// let options = BoardOptions::parse_options("size=9").unwrap();
+/// assert_eq!(BoardOptions { size: 9 }, options);
+/// assert_eq!("size=9", options.to_options_string());
+/// assert!(BoardOptions::parse_options("width=9").is_err());
+/// ```
+pub trait GameOptions: Sized {
+    /// Take all known options from `options`.
+    ///
+    /// Missing options should fall back to defaults.
+    fn from_options(options: &mut Options) -> Result<Self>;
+
+    fn write_options(&self, writer: &mut OptionsWriter);
+
+    /// Parse an option string and reject unknown options.
+    fn parse_options(string: &str) -> Result<Self> {
+        let mut options = Options::parse(string)?;
+        let result = Self::from_options(&mut options)?;
+        options.finish()?;
+        Ok(result)
+    }
+
+    fn to_options_string(&self) -> String {
+        let mut writer = OptionsWriter::new();
+        self.write_options(&mut writer);
+        writer.finish()
+    }
+}
+
+fn invalid(message: String) -> Error {
+    Error::new_dynamic(ErrorCode::InvalidOptions, message)
+}
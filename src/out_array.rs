@@ -0,0 +1,116 @@
+//! Helper for the out-array pattern of the plugin APIs.
+
+use std::{fmt::Debug, marker::PhantomData, slice};
+
+use crate::error::{Error, ErrorCode, Result};
+
+/// Safe wrapper for a caller-provided array with an element count.
+///
+/// Many API functions receive a pointer to an array with a fixed capacity and
+/// a pointer to the count of written elements.
+/// This keeps the count up to date and maps overflows to
+/// [`ErrorCode::OutOfMemory`].
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{error::ErrorCode, out_array::OutArray};
+/// let mut buf = [0u64; 3];
+/// let mut count = 42u32;
+/// let mut array = unsafe { OutArray::new(buf.as_mut_ptr(), buf.len(), &mut count) };
+/// array.push(7).unwrap();
+/// array.extend_from_slice(&[8, 9]).unwrap();
+/// assert_eq!(ErrorCode::OutOfMemory, array.push(10).unwrap_err().code);
+/// assert_eq!(&[7, 8, 9], array.as_slice());
+/// assert_eq!(3, count);
+/// ```
+pub struct OutArray<'l, T, C> {
+    ptr: *mut T,
+    capacity: usize,
+    len: usize,
+    count: *mut C,
+    phantom: PhantomData<(&'l mut [T], &'l mut C)>,
+}
+
+impl<'l, T: Copy, C: TryFrom<usize>> OutArray<'l, T, C> {
+    /// Create a new [`OutArray`] and set the count to zero.
+    ///
+    /// # Safety
+    /// `ptr` must be valid for writing `capacity` elements and `count` must be
+    /// valid for writes.
+    /// Both must stay valid for `'l`.
+    pub unsafe fn new(ptr: *mut T, capacity: usize, count: *mut C) -> Self {
+        let array = Self {
+            ptr,
+            capacity,
+            len: 0,
+            count,
+            phantom: Default::default(),
+        };
+        array.write_count(0).expect("count type cannot hold zero");
+        array
+    }
+
+    /// Append `value` or fail with [`ErrorCode::OutOfMemory`] if full.
+    #[inline]
+    pub fn push(&mut self, value: T) -> Result<()> {
+        self.extend_from_slice(&[value])
+    }
+
+    /// Append all of `values` or fail with [`ErrorCode::OutOfMemory`] if they
+    /// do not fit.
+    ///
+    /// Nothing is appended on failure.
+    pub fn extend_from_slice(&mut self, values: &[T]) -> Result<()> {
+        let new_len = self
+            .len
+            .checked_add(values.len())
+            .filter(|l| *l <= self.capacity)
+            .ok_or_else(|| Error::new_static(ErrorCode::OutOfMemory, "out-array full\0"))?;
+        self.write_count(new_len)?;
+        unsafe {
+            self.ptr
+                .add(self.len)
+                .copy_from_nonoverlapping(values.as_ptr(), values.len());
+        }
+        self.len = new_len;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The elements written so far.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        if self.len == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.ptr, self.len) }
+        }
+    }
+
+    fn write_count(&self, len: usize) -> Result<()> {
+        let count = C::try_from(len)
+            .map_err(|_| Error::new_static(ErrorCode::OutOfMemory, "out-array count overflow\0"))?;
+        unsafe { self.count.write(count) };
+        Ok(())
+    }
+}
+
+impl<'l, T: Copy + Debug, C: TryFrom<usize>> Debug for OutArray<'l, T, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
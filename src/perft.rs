@@ -0,0 +1,135 @@
+//! Move generation tests which count all move sequences up to a depth.
+//!
+//! Compare the counts against reference values of another implementation to
+//! find bugs in move generation.
+
+use crate::{error::Result, game::Game, player::PlayerId, sys::move_code};
+
+/// Number of leaf nodes after `depth` moves.
+///
+/// Every move of every player to move counts as a separate edge, including
+/// moves of [`PlayerId::RAND`].
+/// `game` is left unchanged.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{game::Game, perft::perft};
+/// fn check_start(game: &mut Game) {
+///     assert_eq!(20, perft(game, 1).unwrap());
+///     assert_eq!(400, perft(game, 2).unwrap());
+/// }
+/// ```
+pub fn perft(game: &mut Game, depth: u32) -> Result<u64> {
+    let mut stack = scratch_games(game, depth)?;
+    count(game, depth, &mut stack)
+}
+
+/// Same as [`perft`] but split by the first move.
+///
+/// This helps narrowing down which line contains a wrong move count.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{game::Game, game_init::GameInit, perft::*, player::PlayerId, sys::*};
+/// // Player 1 always chooses between the moves 1 and 2.
+/// unsafe extern "C" fn create(game: *mut game, _: *mut game_init) -> error_code {
+///     (*game).sizer.max_players_to_move = 1;
+///     (*game).sizer.max_moves = 2;
+///     ERR_ERR_OK
+/// }
+/// unsafe extern "C" fn destroy(_: *mut game) -> error_code {
+///     ERR_ERR_OK
+/// }
+/// unsafe extern "C" fn clone(game: *mut game, target: *mut game) -> error_code {
+///     *target = *game;
+///     ERR_ERR_OK
+/// }
+/// unsafe extern "C" fn copy_from(game: *mut game, other: *mut game) -> error_code {
+///     *game = *other;
+///     ERR_ERR_OK
+/// }
+/// unsafe extern "C" fn players_to_move(
+///     _: *mut game, count: *mut u8, players: *mut player_id,
+/// ) -> error_code {
+///     (*count, *players) = (1, 1);
+///     ERR_ERR_OK
+/// }
+/// unsafe extern "C" fn moves(
+///     _: *mut game, _: player_id, count: *mut u32, moves: *mut move_code,
+/// ) -> error_code {
+///     (*count, *moves, *moves.add(1)) = (2, 1, 2);
+///     ERR_ERR_OK
+/// }
+/// unsafe extern "C" fn make_move(_: *mut game, _: player_id, _: move_code) -> error_code {
+///     ERR_ERR_OK
+/// }
+///
+/// let methods = Box::leak(Box::new(game_methods {
+///     create: Some(create),
+///     destroy: Some(destroy),
+///     clone: Some(clone),
+///     copy_from: Some(copy_from),
+///     players_to_move: Some(players_to_move),
+///     get_concrete_moves: Some(moves),
+///     make_move: Some(make_move),
+///     ..Default::default()
+/// }));
+/// let mut game = unsafe { Game::new(methods, GameInit::Default) }.unwrap();
+/// let p = PlayerId::new(1);
+/// assert_eq!(vec![(p, 1, 1), (p, 2, 1)], perft_divide(&mut game, 1).unwrap());
+/// assert_eq!(vec![(p, 1, 4), (p, 2, 4)], perft_divide(&mut game, 3).unwrap());
+/// assert_eq!(8, perft(&mut game, 3).unwrap());
+/// ```
+pub fn perft_divide(game: &mut Game, depth: u32) -> Result<Vec<(PlayerId, move_code, u64)>> {
+    let mut splits = vec![];
+    if depth == 0 {
+        return Ok(splits);
+    }
+    if depth == 1 {
+        // Every move is a single leaf, so no child game is needed.
+        for player in game.players_to_move()? {
+            let moves = game.concrete_moves(player)?;
+            splits.extend(moves.into_iter().map(|code| (player, code, 1)));
+        }
+        return Ok(splits);
+    }
+
+    let mut stack = scratch_games(game, depth)?;
+    let (child, rest) = stack.split_first_mut().expect("no scratch game");
+    for player in game.players_to_move()? {
+        for code in game.concrete_moves(player)? {
+            child.copy_from(game)?;
+            child.make_move(player, code)?;
+            splits.push((player, code, count(child, depth - 1, rest)?));
+        }
+    }
+    Ok(splits)
+}
+
+/// Games reused for every level below the root to avoid allocations.
+fn scratch_games(game: &mut Game, depth: u32) -> Result<Vec<Game>> {
+    (1..depth).map(|_| game.try_clone()).collect()
+}
+
+fn count(game: &mut Game, depth: u32, stack: &mut [Game]) -> Result<u64> {
+    if depth == 0 {
+        return Ok(1);
+    }
+
+    let mut nodes = 0;
+    for player in game.players_to_move()? {
+        let moves = game.concrete_moves(player)?;
+        if depth == 1 {
+            nodes += moves.len() as u64;
+            continue;
+        }
+
+        let (child, rest) = stack.split_first_mut().expect("no scratch game");
+        for code in moves {
+            child.copy_from(game)?;
+            child.make_move(player, code)?;
+            nodes += count(child, depth - 1, rest)?;
+        }
+    }
+    Ok(nodes)
+}
@@ -0,0 +1,119 @@
+//! Type-safe wrapper around [`player_id`].
+
+use std::{fmt::Display, slice};
+
+use crate::sys::{player_id, PLAYER_NONE, PLAYER_RAND};
+
+/// A _surena_ player ID.
+///
+/// Regular players are numbered starting at 1.
+/// [`PlayerId::NONE`] and [`PlayerId::RAND`] are the special IDs for "no
+/// player" and the random player.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::player::PlayerId;
+/// let players: Vec<_> = PlayerId::iter(2).collect();
+/// assert_eq!(vec![PlayerId::new(1), PlayerId::new(2)], players);
+/// assert_eq!(Some(1), players[1].index());
+/// assert!(!PlayerId::RAND.is_regular());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
+pub struct PlayerId(player_id);
+
+impl PlayerId {
+    pub const NONE: Self = Self(PLAYER_NONE);
+    pub const RAND: Self = Self(PLAYER_RAND);
+
+    #[inline]
+    pub const fn new(id: player_id) -> Self {
+        Self(id)
+    }
+
+    #[inline]
+    pub const fn get(self) -> player_id {
+        self.0
+    }
+
+    /// Returns `true` if this is neither [`Self::NONE`] nor [`Self::RAND`].
+    #[inline]
+    pub const fn is_regular(self) -> bool {
+        self.0 != PLAYER_NONE && self.0 != PLAYER_RAND
+    }
+
+    /// Zero-based index for regular players, e.g., into a score array.
+    #[inline]
+    pub fn index(self) -> Option<usize> {
+        self.is_regular().then(|| usize::from(self.0 - 1))
+    }
+
+    /// Inverse of [`Self::index`].
+    ///
+    /// Returns [`None`] if the `index` does not belong to a regular player.
+    #[inline]
+    pub fn from_index(index: usize) -> Option<Self> {
+        let id = Self(index.checked_add(1)?.try_into().ok()?);
+        id.is_regular().then_some(id)
+    }
+
+    /// Iterate over the regular players of a game with `count` players.
+    pub fn iter(count: u8) -> impl Iterator<Item = Self> + Clone {
+        (1..=count).map(Self).filter(|p| p.is_regular())
+    }
+
+    /// View a slice of raw IDs as [`PlayerId`]s.
+    #[inline]
+    pub fn from_raw_slice(players: &[player_id]) -> &[Self] {
+        // PlayerId is a transparent wrapper.
+        unsafe { slice::from_raw_parts(players.as_ptr().cast(), players.len()) }
+    }
+
+    /// View a slice of [`PlayerId`]s as raw IDs, e.g., to copy it into an
+    /// out-array of the C API.
+    #[inline]
+    pub fn as_raw_slice(players: &[Self]) -> &[player_id] {
+        // PlayerId is a transparent wrapper.
+        unsafe { slice::from_raw_parts(players.as_ptr().cast(), players.len()) }
+    }
+}
+
+impl From<player_id> for PlayerId {
+    #[inline]
+    fn from(id: player_id) -> Self {
+        Self(id)
+    }
+}
+
+impl From<PlayerId> for player_id {
+    #[inline]
+    fn from(id: PlayerId) -> Self {
+        id.0
+    }
+}
+
+impl Display for PlayerId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::NONE => write!(f, "none"),
+            Self::RAND => write!(f, "random"),
+            Self(id) => write!(f, "{id}"),
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for PlayerId {
+    /// Generates any ID including [`PlayerId::NONE`] and [`PlayerId::RAND`].
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self(u.arbitrary()?))
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <player_id as arbitrary::Arbitrary>::size_hint(depth)
+    }
+}
@@ -0,0 +1,92 @@
+//! Recycling of [`Game`] instances for engines.
+
+use crate::{error::Result, game::Game};
+
+/// Pool of unused games which are reused instead of cloned.
+///
+/// Engines often clone a game for every simulation, e.g., in MCTS.
+/// [`GamePool::get`] overwrites a recycled game via [`Game::copy_from`] when
+/// possible, which replaces a `clone` and a `destroy` call with a single
+/// `copy_from` call.
+/// Whether this is faster depends on the game, so measure before relying on
+/// it.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{error::Result, game::Game, pool::GamePool};
+/// fn simulate(pool: &mut GamePool, root: &mut Game) -> Result<()> {
+///     for _ in 0..1000 {
+///         let mut game = pool.get(root)?;
+///         // Play out `game` ...
+///         pool.recycle(game);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct GamePool {
+    free: Vec<Game>,
+    limit: usize,
+}
+
+impl GamePool {
+    /// Create a pool without a limit.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_limit(usize::MAX)
+    }
+
+    /// Create a pool which keeps at most `limit` unused games.
+    #[inline]
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            free: vec![],
+            limit,
+        }
+    }
+
+    /// Returns a copy of `source`, reusing a recycled game if possible.
+    ///
+    /// A recycled game which cannot be overwritten is destroyed.
+    pub fn get(&mut self, source: &mut Game) -> Result<Game> {
+        if let Some(index) = self.free.iter().rposition(|g| g.same_methods(source)) {
+            let mut game = self.free.swap_remove(index);
+            if game.copy_from(source).is_ok() {
+                return Ok(game);
+            }
+        }
+        source.try_clone()
+    }
+
+    /// Return `game` to the pool for reuse.
+    ///
+    /// It is destroyed instead if the pool is full.
+    pub fn recycle(&mut self, game: Game) {
+        if self.free.len() < self.limit {
+            self.free.push(game);
+        }
+    }
+
+    /// Number of unused games in the pool.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.free.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.free.is_empty()
+    }
+
+    /// Destroy all unused games.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.free.clear();
+    }
+}
+
+impl Default for GamePool {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,356 @@
+//! Helpers for games with randomness.
+//!
+//! _surena_ passes plain `u64` seeds to `discretize` and `playout`.
+
+/// Seed for the random parts of a game.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::random::Seed;
+/// assert!(Seed::NONE.is_none());
+/// let seed = Seed::new(1234);
+/// assert_eq!(Some(1234), seed.get());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
+pub struct Seed(u64);
+
+impl Seed {
+    /// Special seed meaning that no seed is given.
+    pub const NONE: Self = Self(0);
+
+    #[inline]
+    pub const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Returns the seed or [`None`] for [`Seed::NONE`].
+    #[inline]
+    pub const fn get(self) -> Option<u64> {
+        if self.is_none() {
+            None
+        } else {
+            Some(self.0)
+        }
+    }
+
+    #[inline]
+    pub const fn is_none(self) -> bool {
+        self.0 == Self::NONE.0
+    }
+
+    /// Create a [`FastPrng`] from this seed.
+    ///
+    /// The same seed always results in the same sequence.
+    #[inline]
+    pub fn rng(self) -> FastPrng {
+        FastPrng::new(self.0, 0)
+    }
+}
+
+impl Seed {
+    /// Derive a seed from a string, e.g., the game options.
+    ///
+    /// This uses the 64-bit FNV-1a hash, so it is stable across platforms and
+    /// versions.
+    ///
+    /// # Example
+    /// ```
+    /// # use mirabel_sys::random::Seed;
+    /// assert_eq!(Seed::from_str_hash("9x9"), Seed::from_str_hash("9x9"));
+    /// assert_ne!(Seed::from_str_hash("9x9"), Seed::from_str_hash("19x19"));
+    /// ```
+    pub fn from_str_hash(string: &str) -> Self {
+        let hash = string.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        });
+        Self(hash)
+    }
+}
+
+impl From<u64> for Seed {
+    #[inline]
+    fn from(seed: u64) -> Self {
+        Self(seed)
+    }
+}
+
+impl From<Seed> for u64 {
+    #[inline]
+    fn from(seed: Seed) -> Self {
+        seed.0
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Seed {
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self(u.arbitrary()?))
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <u64 as arbitrary::Arbitrary>::size_hint(depth)
+    }
+}
+
+/// Small and fast PCG32 random number generator.
+///
+/// This follows the PCG32 reference implementation (`pcg32_srandom_r` and
+/// `pcg32_random_r`), so sequences match C games using the same generator.
+/// It is not suitable for cryptography.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::random::FastPrng;
+/// let mut rng = FastPrng::new(42, 54);
+/// assert_eq!(0xa15c02b7, rng.next_u32());
+/// assert_eq!(0x7b47f409, rng.next_u32());
+/// assert!(rng.below(6) < 6);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FastPrng {
+    state: u64,
+    inc: u64,
+}
+
+impl FastPrng {
+    const MULTIPLIER: u64 = 6364136223846793005;
+
+    /// Seed the generator with an initial state and a sequence selector.
+    pub fn new(init_state: u64, init_seq: u64) -> Self {
+        let mut rng = Self {
+            state: 0,
+            inc: (init_seq << 1) | 1,
+        };
+        rng.next_u32();
+        rng.state = rng.state.wrapping_add(init_state);
+        rng.next_u32();
+        rng
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = old.wrapping_mul(Self::MULTIPLIER).wrapping_add(self.inc);
+        let xor_shifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rot = (old >> 59) as u32;
+        xor_shifted.rotate_right(rot)
+    }
+
+    /// Uniformly distributed number in `0..bound`.
+    ///
+    /// # Panics
+    /// Panics if `bound` is zero.
+    pub fn below(&mut self, bound: u32) -> u32 {
+        assert_ne!(0, bound, "empty range");
+        // Reject the lowest values to remove the modulo bias.
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let r = self.next_u32();
+            if r >= threshold {
+                return r % bound;
+            }
+        }
+    }
+}
+
+/// One step of the SplitMix64 generator usable in `const` contexts.
+///
+/// Returns the next state and the generated number.
+#[inline]
+pub const fn splitmix64(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    (state, z ^ (z >> 31))
+}
+
+/// Table of `N` pseudo-random numbers, e.g., Zobrist keys, generated at
+/// compile time.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::random::random_table;
+/// const KEYS: [u64; 64] = random_table(42);
+/// assert_ne!(KEYS[0], KEYS[1]);
+/// assert_eq!(KEYS, random_table::<64>(42));
+/// ```
+pub const fn random_table<const N: usize>(seed: u64) -> [u64; N] {
+    let mut table = [0; N];
+    let mut state = seed;
+    let mut i = 0;
+    while i < N {
+        let (next, value) = splitmix64(state);
+        state = next;
+        table[i] = value;
+        i += 1;
+    }
+    table
+}
+
+/// Zobrist keys for position hashes of board games.
+///
+/// Each combination of a piece type and a board cell has a random key.
+/// The hash of a position is the XOR of the keys of all occupied cells, so
+/// it can be updated incrementally when making and unmaking moves.
+///
+/// The result is suitable for the `id` game method.
+/// Implemented by [`Zobrist`] for boards known at compile time and by
+/// [`StateHasher`] for boards known at runtime.
+pub trait ZobristKeys {
+    /// Key of `piece` on `cell`.
+    ///
+    /// # Panics
+    /// Panics if `piece` or `cell` is out of range.
+    fn key(&self, piece: usize, cell: usize) -> u64;
+
+    /// Key for switching the player to move.
+    fn side_key(&self) -> u64;
+
+    /// Add or remove `piece` on `cell` to or from `hash`.
+    #[inline]
+    fn toggle(&self, hash: &mut u64, piece: usize, cell: usize) {
+        *hash ^= self.key(piece, cell);
+    }
+
+    #[inline]
+    fn toggle_side(&self, hash: &mut u64) {
+        *hash ^= self.side_key();
+    }
+
+    /// Compute the hash of the given `(piece, cell)` pairs from scratch.
+    ///
+    /// This is useful for checking incremental updates in debug builds.
+    fn hash(&self, pieces: impl IntoIterator<Item = (usize, usize)>) -> u64
+    where
+        Self: Sized,
+    {
+        pieces
+            .into_iter()
+            .fold(0, |hash, (piece, cell)| hash ^ self.key(piece, cell))
+    }
+}
+
+/// [`ZobristKeys`] for `PIECES` piece types on `CELLS` board cells,
+/// generated at compile time.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::random::{Zobrist, ZobristKeys};
+/// static ZOBRIST: Zobrist<2, 9> = Zobrist::new(7);
+///
+/// let mut hash = ZOBRIST.hash([(0, 4)]);
+/// ZOBRIST.toggle(&mut hash, 1, 0); // place
+/// ZOBRIST.toggle_side(&mut hash);
+/// assert_eq!(ZOBRIST.hash([(0, 4), (1, 0)]) ^ ZOBRIST.side_key(), hash);
+///
+/// ZOBRIST.toggle(&mut hash, 1, 0); // unmake
+/// ZOBRIST.toggle_side(&mut hash);
+/// assert_eq!(ZOBRIST.hash([(0, 4)]), hash);
+///
+/// const CORNER: u64 = Zobrist::<2, 9>::new(7).key(0, 0);
+/// assert_eq!(CORNER, ZOBRIST.key(0, 0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Zobrist<const PIECES: usize, const CELLS: usize> {
+    keys: [[u64; CELLS]; PIECES],
+    side: u64,
+}
+
+impl<const PIECES: usize, const CELLS: usize> Zobrist<PIECES, CELLS> {
+    /// Generate the keys from `seed`, also at compile time.
+    pub const fn new(seed: u64) -> Self {
+        let mut keys = [[0; CELLS]; PIECES];
+        let mut state = seed;
+        let mut piece = 0;
+        while piece < PIECES {
+            let mut cell = 0;
+            while cell < CELLS {
+                let (next, key) = splitmix64(state);
+                state = next;
+                keys[piece][cell] = key;
+                cell += 1;
+            }
+            piece += 1;
+        }
+        let (_, side) = splitmix64(state);
+        Self { keys, side }
+    }
+
+    /// Same as [`ZobristKeys::key`] but usable in `const` contexts.
+    #[inline]
+    pub const fn key(&self, piece: usize, cell: usize) -> u64 {
+        self.keys[piece][cell]
+    }
+
+    /// Same as [`ZobristKeys::side_key`] but usable in `const` contexts.
+    #[inline]
+    pub const fn side_key(&self) -> u64 {
+        self.side
+    }
+}
+
+impl<const PIECES: usize, const CELLS: usize> ZobristKeys for Zobrist<PIECES, CELLS> {
+    #[inline]
+    fn key(&self, piece: usize, cell: usize) -> u64 {
+        self.keys[piece][cell]
+    }
+
+    #[inline]
+    fn side_key(&self) -> u64 {
+        self.side
+    }
+}
+
+/// [`ZobristKeys`] for boards whose size is only known at runtime.
+///
+/// The keys are generated from the game options, so the same options always
+/// result in the same hashes.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::random::{StateHasher, ZobristKeys};
+/// let hasher = StateHasher::new("size=9", 2, 81);
+/// let mut hash = 0;
+/// hasher.toggle(&mut hash, 0, 40);
+/// assert_eq!(hasher.hash([(0, 40)]), hash);
+/// assert_eq!(hasher.key(1, 80), StateHasher::new("size=9", 2, 81).key(1, 80));
+/// ```
+#[derive(Debug, Clone)]
+pub struct StateHasher {
+    keys: Vec<u64>,
+    cells: usize,
+    side: u64,
+}
+
+impl StateHasher {
+    /// Generate keys for `pieces` piece types on `cells` cells.
+    pub fn new(options: &str, pieces: usize, cells: usize) -> Self {
+        let mut state = Seed::from_str_hash(options).0;
+        let mut next = || {
+            let (next, key) = splitmix64(state);
+            state = next;
+            key
+        };
+        let keys = (0..pieces * cells).map(|_| next()).collect();
+        let side = next();
+        Self { keys, cells, side }
+    }
+}
+
+impl ZobristKeys for StateHasher {
+    #[inline]
+    fn key(&self, piece: usize, cell: usize) -> u64 {
+        assert!(cell < self.cells, "cell out of range");
+        self.keys[piece * self.cells + cell]
+    }
+
+    #[inline]
+    fn side_key(&self) -> u64 {
+        self.side
+    }
+}
@@ -0,0 +1,176 @@
+//! Move history of a game, e.g., for move lists and takebacks.
+
+use std::{fmt::Write, time::Duration};
+
+use crate::{
+    error::{Error, ErrorCode, Result},
+    game::Game,
+    game_init::OwnedGameInit,
+    player::PlayerId,
+    sys::move_code,
+};
+
+/// A single recorded move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayMove {
+    pub player: PlayerId,
+    pub code: move_code,
+    /// Clock of `player` after the move, e.g., the remaining time.
+    pub clock: Option<Duration>,
+}
+
+/// Record of all moves made since a game was created.
+///
+/// Moves can be undone by re-creating the game from its [`OwnedGameInit`]
+/// and replaying the remaining moves, so games need not support undoing
+/// moves themselves.
+///
+/// The text notation contains one `player:code` token per move, optionally
+/// followed by `@` and the clock in milliseconds, e.g., `1:12 2:7@59000`.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{game_init::OwnedGameInit, player::PlayerId, replay::*};
+/// let mut replay = Replay::new(OwnedGameInit::Default);
+/// replay.record(PlayerId::new(1), 12, None);
+/// replay.record(PlayerId::new(2), 7, Some(std::time::Duration::from_secs(59)));
+/// assert_eq!("1:12 2:7@59000", replay.export_moves());
+///
+/// let imported = Replay::import_moves(OwnedGameInit::Default, "1:12 2:7@59000").unwrap();
+/// assert_eq!(replay.moves(), imported.moves());
+/// assert!(Replay::import_moves(OwnedGameInit::Default, "1:x").is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Replay {
+    init: OwnedGameInit,
+    moves: Vec<ReplayMove>,
+}
+
+impl Replay {
+    /// Start an empty replay for a game created with `init`.
+    #[inline]
+    pub fn new(init: OwnedGameInit) -> Self {
+        Self {
+            init,
+            moves: vec![],
+        }
+    }
+
+    #[inline]
+    pub fn init(&self) -> &OwnedGameInit {
+        &self.init
+    }
+
+    #[inline]
+    pub fn moves(&self) -> &[ReplayMove] {
+        &self.moves
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// Append a move which has already been made.
+    #[inline]
+    pub fn record(&mut self, player: PlayerId, code: move_code, clock: Option<Duration>) {
+        self.moves.push(ReplayMove {
+            player,
+            code,
+            clock,
+        });
+    }
+
+    /// Make a move on `game` and record it on success.
+    pub fn play(
+        &mut self,
+        game: &mut Game,
+        player: PlayerId,
+        code: move_code,
+        clock: Option<Duration>,
+    ) -> Result<()> {
+        game.make_move(player, code)?;
+        self.record(player, code, clock);
+        Ok(())
+    }
+
+    /// Keep only the first `len` moves and reset `game` accordingly.
+    ///
+    /// `game` is replaced by a new game with the same methods.
+    /// On error, neither `game` nor the replay are changed.
+    pub fn truncate(&mut self, game: &mut Game, len: usize) -> Result<()> {
+        let len = len.min(self.moves.len());
+        *game = self.rebuild(game, len)?;
+        self.moves.truncate(len);
+        Ok(())
+    }
+
+    /// Take back the last `count` moves.
+    ///
+    /// See [`Replay::truncate`].
+    #[inline]
+    pub fn undo(&mut self, game: &mut Game, count: usize) -> Result<()> {
+        self.truncate(game, self.moves.len().saturating_sub(count))
+    }
+
+    /// Create a new game with the methods of `game` and replay the first
+    /// `len` moves.
+    ///
+    /// # Panics
+    /// Panics if `len` is greater than [`Replay::len`].
+    pub fn rebuild(&self, game: &Game, len: usize) -> Result<Game> {
+        // `game` proves that its methods are still valid.
+        let mut new = unsafe { Game::new(game.methods(), self.init.as_game_init())? };
+        for m in &self.moves[..len] {
+            new.make_move(m.player, m.code)?;
+        }
+        Ok(new)
+    }
+
+    /// Write all moves in the text notation.
+    pub fn export_moves(&self) -> String {
+        let mut string = String::new();
+        for m in &self.moves {
+            if !string.is_empty() {
+                string.push(' ');
+            }
+            write!(string, "{}:{}", m.player.get(), m.code).unwrap();
+            if let Some(clock) = m.clock {
+                write!(string, "@{}", clock.as_millis()).unwrap();
+            }
+        }
+        string
+    }
+
+    /// Parse moves written by [`Replay::export_moves`].
+    ///
+    /// The moves are not validated against a game.
+    pub fn import_moves(init: OwnedGameInit, string: &str) -> Result<Self> {
+        let mut replay = Self::new(init);
+        for token in string.split_whitespace() {
+            let m = parse_move(token).ok_or_else(|| {
+                Error::new_dynamic(ErrorCode::InvalidInput, format!("invalid move {token:?}"))
+            })?;
+            replay.moves.push(m);
+        }
+        Ok(replay)
+    }
+}
+
+fn parse_move(token: &str) -> Option<ReplayMove> {
+    let (player, rest) = token.split_once(':')?;
+    let (code, clock) = match rest.split_once('@') {
+        Some((code, clock)) => (code, Some(Duration::from_millis(clock.parse().ok()?))),
+        None => (rest, None),
+    };
+    Some(ReplayMove {
+        player: PlayerId::new(player.parse().ok()?),
+        code: code.parse().ok()?,
+        clock,
+    })
+}
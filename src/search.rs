@@ -0,0 +1,192 @@
+//! Helpers for running searches on worker threads.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc::{self, Receiver, Sender},
+    Arc,
+};
+
+#[cfg(feature = "mirabel")]
+use crate::{display_data::DisplayData, event::EventAny, player::PlayerId, sys::move_code};
+
+/// Shared stop flag for a search running on another thread.
+///
+/// Clones share the same flag, so one clone can be moved into the worker
+/// while another one stops it.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::search::SearchHandle;
+/// let handle = SearchHandle::new();
+/// let worker = {
+///     let handle = handle.clone();
+///     std::thread::spawn(move || {
+///         let mut checker = handle.checker(1024);
+///         let mut nodes = 0u64;
+///         while !checker.should_stop() {
+///             nodes += 1;
+///         }
+///         nodes
+///     })
+/// };
+/// handle.stop();
+/// let _nodes = worker.join().unwrap();
+/// assert!(handle.should_stop());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SearchHandle {
+    stop: Arc<AtomicBool>,
+}
+
+impl SearchHandle {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request the search to stop.
+    #[inline]
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` once [`SearchHandle::stop`] has been called.
+    #[inline]
+    pub fn should_stop(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    /// Allow reusing the handle for the next search.
+    #[inline]
+    pub fn reset(&self) {
+        self.stop.store(false, Ordering::Relaxed);
+    }
+
+    /// Create a [`StopChecker`] which only reads the flag every `interval`
+    /// checks.
+    ///
+    /// # Panics
+    /// Panics if `interval` is zero.
+    pub fn checker(&self, interval: u32) -> StopChecker<'_> {
+        assert_ne!(0, interval, "zero check interval");
+        StopChecker {
+            handle: self,
+            interval,
+            countdown: 0,
+        }
+    }
+}
+
+/// Cheap periodic check of a [`SearchHandle`] for hot search loops.
+#[derive(Debug)]
+pub struct StopChecker<'l> {
+    handle: &'l SearchHandle,
+    interval: u32,
+    countdown: u32,
+}
+
+impl<'l> StopChecker<'l> {
+    /// Returns `true` if the search should stop.
+    ///
+    /// The first call always reads the flag.
+    #[inline]
+    pub fn should_stop(&mut self) -> bool {
+        if self.countdown == 0 {
+            self.countdown = self.interval;
+            if self.handle.should_stop() {
+                // Keep reporting the stop on every following call.
+                self.countdown = 0;
+                return true;
+            }
+        }
+        self.countdown -= 1;
+        false
+    }
+}
+
+/// Create a channel for handing the best move of a worker search to the
+/// thread which owns the event queue.
+///
+/// With the `mirabel` feature, [`BestMoveReceiver::emit`] turns the best
+/// move into a game move event.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::search::best_move_channel;
+/// let (sender, receiver) = best_move_channel();
+/// std::thread::spawn(move || {
+///     sender.send(7);
+///     sender.send(12);
+/// })
+/// .join()
+/// .unwrap();
+/// assert_eq!(Some(12), receiver.latest());
+/// assert_eq!(None, receiver.latest());
+/// ```
+pub fn best_move_channel<M>() -> (BestMoveSender<M>, BestMoveReceiver<M>) {
+    let (sender, receiver) = mpsc::channel();
+    (BestMoveSender(sender), BestMoveReceiver(receiver))
+}
+
+/// Sending half of [`best_move_channel`], e.g., for the worker thread.
+#[derive(Debug)]
+pub struct BestMoveSender<M>(Sender<M>);
+
+impl<M> BestMoveSender<M> {
+    /// Report `best` as the current best move.
+    ///
+    /// Returns `false` if the receiver has been dropped, so the search can
+    /// be abandoned.
+    #[inline]
+    pub fn send(&self, best: M) -> bool {
+        self.0.send(best).is_ok()
+    }
+}
+
+impl<M> Clone for BestMoveSender<M> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// Receiving half of [`best_move_channel`].
+#[derive(Debug)]
+pub struct BestMoveReceiver<M>(Receiver<M>);
+
+impl<M> BestMoveReceiver<M> {
+    /// The most recently sent best move, if any was sent since the last
+    /// call.
+    ///
+    /// Older moves are superseded and discarded.
+    /// This never blocks.
+    #[inline]
+    pub fn latest(&self) -> Option<M> {
+        self.0.try_iter().last()
+    }
+}
+
+#[cfg(feature = "mirabel")]
+impl BestMoveReceiver<(PlayerId, move_code)> {
+    /// Push the [`latest`](Self::latest) best move as a game move event.
+    ///
+    /// Returns `true` if an event was pushed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use mirabel_sys::{display_data::DisplayData, search::*};
+    /// # use mirabel_sys::{player::PlayerId, sys::move_code};
+    /// fn update(dd: &mut DisplayData, search: &BestMoveReceiver<(PlayerId, move_code)>) {
+    ///     search.emit(dd);
+    /// }
+    /// ```
+    pub fn emit(&self, dd: &mut DisplayData) -> bool {
+        match self.latest() {
+            Some((player, code)) => {
+                dd.push_event(EventAny::new_game_move(player, code));
+                true
+            }
+            None => false,
+        }
+    }
+}
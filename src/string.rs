@@ -1,12 +1,13 @@
 //! Helpers for dealing with C-style strings safely.
 
 use std::{
-    ffi::{c_char, CStr, CString, FromBytesWithNulError, NulError},
+    ffi::{c_char, CStr, CString, FromBytesWithNulError, IntoStringError, NulError},
     fmt::{Debug, Display},
     marker::PhantomData,
+    mem,
     ops::Deref,
     ptr::NonNull,
-    str::from_utf8_unchecked,
+    str::{from_utf8_unchecked, Utf8Error},
 };
 
 /// Simple helper function to create a [`ValidCStr`] from an [`str`].
@@ -20,7 +21,7 @@ use std::{
 /// cstr("my C-style string\0");
 /// ```
 #[inline]
-pub fn cstr(string: &str) -> ValidCStr {
+pub fn cstr(string: &str) -> ValidCStr<'_> {
     string.try_into().expect("invalid C string")
 }
 
@@ -88,6 +89,66 @@ impl<'s> ValidCStr<'s> {
             phantom: Default::default(),
         })
     }
+
+    /// Create a new ValidCStr from a NUL-terminated [`str`].
+    ///
+    /// Unlike [`cstr`], this can be evaluated at compile time.
+    /// Prefer the [`cstr!`](crate::cstr!) macro for literals.
+    ///
+    /// # Panics
+    /// `string` must be NUL-terminated and must not contain any other NUL
+    /// byte.
+    /// In `const` contexts, this results in a compilation error.
+    ///
+    /// # Example
+    /// ```
+    /// # use mirabel_sys::ValidCStr;
+    /// const LABEL: ValidCStr<'static> = ValidCStr::from_str_with_nul("label\0");
+    /// assert_eq!("label", LABEL.to_string());
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use mirabel_sys::ValidCStr;
+    /// const LABEL: ValidCStr<'static> = ValidCStr::from_str_with_nul("la\0bel\0");
+    /// ```
+    pub const fn from_str_with_nul(string: &'s str) -> Self {
+        let bytes = string.as_bytes();
+        assert!(
+            !bytes.is_empty() && bytes[bytes.len() - 1] == 0,
+            "missing NUL terminator"
+        );
+        let mut i = 0;
+        while i < bytes.len() - 1 {
+            assert!(bytes[i] != 0, "interior NUL byte");
+            i += 1;
+        }
+
+        Self {
+            // A str pointer is never NULL.
+            cstr: unsafe { NonNull::new_unchecked(bytes.as_ptr() as *mut c_char) },
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// Create a [`ValidCStr`] from a string literal at compile time.
+///
+/// This appends the NUL terminator and fails compilation if the literal
+/// contains a NUL byte.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{cstr, ValidCStr};
+/// let label: ValidCStr<'static> = cstr!("board size");
+/// assert_eq!("board size", label.to_string());
+/// ```
+#[macro_export]
+macro_rules! cstr {
+    ($string: literal) => {{
+        const CSTR: $crate::ValidCStr<'static> =
+            $crate::ValidCStr::from_str_with_nul(::std::concat!($string, "\0"));
+        CSTR
+    }};
 }
 
 impl<'s> Display for ValidCStr<'s> {
@@ -112,6 +173,34 @@ impl<'l> TryFrom<&'l str> for ValidCStr<'l> {
     }
 }
 
+impl<'l> TryFrom<&'l CStr> for ValidCStr<'l> {
+    type Error = Utf8Error;
+
+    /// Validates that `value` is UTF-8.
+    ///
+    /// # Example
+    /// ```
+    /// # use mirabel_sys::ValidCStr;
+    /// # use std::ffi::CStr;
+    /// let cstr = CStr::from_bytes_with_nul(b"SDL\0").unwrap();
+    /// assert_eq!("SDL", ValidCStr::try_from(cstr).unwrap().to_string());
+    /// let invalid = CStr::from_bytes_with_nul(b"\xff\0").unwrap();
+    /// assert!(ValidCStr::try_from(invalid).is_err());
+    /// ```
+    #[inline]
+    fn try_from(value: &'l CStr) -> Result<Self, Self::Error> {
+        value.to_str()?;
+        unsafe { Ok(Self::new(value.as_ptr()).unwrap_unchecked()) }
+    }
+}
+
+impl<'s> AsRef<CStr> for ValidCStr<'s> {
+    #[inline]
+    fn as_ref(&self) -> &CStr {
+        (*self).into()
+    }
+}
+
 impl<'l> From<ValidCStr<'l>> for *const c_char {
     #[inline]
     fn from(s: ValidCStr<'l>) -> Self {
@@ -134,8 +223,58 @@ impl<'l> From<ValidCStr<'l>> for &'l str {
 /// Owned variant of [`ValidCStr`] which simply wraps a [`CString`].
 ///
 /// It additionally guarantees that the character sequence is valid UTF-8.
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ValidCString(CString);
 
+impl ValidCString {
+    /// Borrow this string as a [`ValidCStr`].
+    ///
+    /// # Example
+    /// ```
+    /// # use mirabel_sys::{ValidCStr, ValidCString};
+    /// let string = ValidCString::try_from("borrowed").unwrap();
+    /// let s: ValidCStr = string.as_valid_cstr();
+    /// assert_eq!("borrowed", s.to_string());
+    /// ```
+    #[inline]
+    pub fn as_valid_cstr(&self) -> ValidCStr<'_> {
+        // A CString is never NULL and always NUL-terminated.
+        unsafe { ValidCStr::new(self.0.as_ptr()).unwrap_unchecked() }
+    }
+
+    /// Convert this string back into a Rust [`String`].
+    #[inline]
+    pub fn into_string(self) -> String {
+        unsafe { String::from_utf8_unchecked(self.0.into_bytes()) }
+    }
+
+    /// Append `string` to the end of this string.
+    ///
+    /// Fails if `string` contains a NUL byte and leaves this string unchanged
+    /// in this case.
+    /// The position in the error is relative to `string`.
+    ///
+    /// # Example
+    /// ```
+    /// # use mirabel_sys::ValidCString;
+    /// let mut string = ValidCString::try_from("board").unwrap();
+    /// string.push_str(" size").unwrap();
+    /// assert!(string.push_str("\0").is_err());
+    /// assert_eq!("board size", string.into_string());
+    /// ```
+    pub fn push_str(&mut self, string: &str) -> Result<(), NulError> {
+        if string.contains('\0') {
+            return Err(CString::new(string).unwrap_err());
+        }
+
+        let mut bytes = mem::take(&mut self.0).into_bytes();
+        bytes.extend_from_slice(string.as_bytes());
+        // Both parts have just been checked for NUL bytes.
+        self.0 = unsafe { CString::from_vec_unchecked(bytes) };
+        Ok(())
+    }
+}
+
 impl TryFrom<String> for ValidCString {
     type Error = NulError;
 
@@ -153,6 +292,64 @@ impl TryFrom<String> for ValidCString {
     }
 }
 
+impl TryFrom<CString> for ValidCString {
+    type Error = IntoStringError;
+
+    /// Validates that `value` is UTF-8.
+    ///
+    /// The error allows recovering the original [`CString`].
+    fn try_from(value: CString) -> Result<Self, Self::Error> {
+        let bytes = value.into_string()?.into_bytes();
+        // The bytes came from a CString, so they contain no NUL byte.
+        Ok(Self(unsafe { CString::from_vec_unchecked(bytes) }))
+    }
+}
+
+impl From<ValidCString> for CString {
+    #[inline]
+    fn from(s: ValidCString) -> Self {
+        s.0
+    }
+}
+
+impl AsRef<CStr> for ValidCString {
+    #[inline]
+    fn as_ref(&self) -> &CStr {
+        &self.0
+    }
+}
+
+impl TryFrom<&str> for ValidCString {
+    type Error = NulError;
+
+    /// Wrapper for [`CString::new`].
+    #[inline]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        CString::new(value).map(Self)
+    }
+}
+
+impl<'l> From<ValidCStr<'l>> for ValidCString {
+    #[inline]
+    fn from(s: ValidCStr<'l>) -> Self {
+        Self(Into::<&CStr>::into(s).to_owned())
+    }
+}
+
+impl<'l> From<&'l ValidCString> for ValidCStr<'l> {
+    #[inline]
+    fn from(s: &'l ValidCString) -> Self {
+        s.as_valid_cstr()
+    }
+}
+
+impl From<ValidCString> for String {
+    #[inline]
+    fn from(s: ValidCString) -> Self {
+        s.into_string()
+    }
+}
+
 impl<'l> From<&'l ValidCString> for &'l str {
     #[inline]
     fn from(s: &'l ValidCString) -> Self {
@@ -180,3 +377,85 @@ impl Debug for ValidCString {
         Debug::fmt(Into::<&str>::into(self), f)
     }
 }
+
+/// Either a borrowed [`ValidCStr`] or an owned [`ValidCString`].
+///
+/// This avoids allocations for strings which are only sometimes dynamic.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{cstr, CowCStr, ValidCString};
+/// fn label(count: u32) -> CowCStr<'static> {
+///     if count == 1 {
+///         cstr!("one piece").into()
+///     } else {
+///         ValidCString::try_from(format!("{count} pieces")).unwrap().into()
+///     }
+/// }
+/// assert_eq!("one piece", label(1).to_string());
+/// assert_eq!("3 pieces", label(3).to_string());
+/// ```
+#[derive(Clone)]
+pub enum CowCStr<'s> {
+    Borrowed(ValidCStr<'s>),
+    Owned(ValidCString),
+}
+
+impl<'s> CowCStr<'s> {
+    /// Pointer to the NUL-terminated string.
+    ///
+    /// It is only valid as long as `self` is neither dropped nor modified.
+    #[inline]
+    pub fn as_ptr(&self) -> *const c_char {
+        self.as_valid_cstr().into()
+    }
+
+    #[inline]
+    pub fn as_valid_cstr(&self) -> ValidCStr<'_> {
+        match self {
+            CowCStr::Borrowed(s) => *s,
+            CowCStr::Owned(s) => s.as_valid_cstr(),
+        }
+    }
+
+    /// Copy a borrowed string so that it no longer depends on `'s`.
+    pub fn into_owned(self) -> ValidCString {
+        match self {
+            CowCStr::Borrowed(s) => s.into(),
+            CowCStr::Owned(s) => s,
+        }
+    }
+}
+
+impl<'s> From<ValidCStr<'s>> for CowCStr<'s> {
+    #[inline]
+    fn from(s: ValidCStr<'s>) -> Self {
+        Self::Borrowed(s)
+    }
+}
+
+impl From<ValidCString> for CowCStr<'_> {
+    #[inline]
+    fn from(s: ValidCString) -> Self {
+        Self::Owned(s)
+    }
+}
+
+impl<'s> From<&'s CowCStr<'_>> for ValidCStr<'s> {
+    #[inline]
+    fn from(s: &'s CowCStr<'_>) -> Self {
+        s.as_valid_cstr()
+    }
+}
+
+impl<'s> Display for CowCStr<'s> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.as_valid_cstr(), f)
+    }
+}
+
+impl<'s> Debug for CowCStr<'s> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.as_valid_cstr(), f)
+    }
+}
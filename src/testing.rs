@@ -0,0 +1,398 @@
+//! Conformance checks and assertions for game plugins, e.g., in their test
+//! suites.
+
+use std::fmt::Display;
+
+use crate::{
+    error::{Error, ErrorCode, ErrorString},
+    game::{Game, GameMethodsRef},
+    game_init::{GameInit, OwnedGameInit},
+    player::PlayerId,
+    random::Seed,
+    sys::{move_code, MOVE_NONE},
+};
+
+/// Plays a random game and checks the game methods along the way.
+///
+/// Checked are:
+/// - repeated creation and destruction,
+/// - state export/import round trips,
+/// - serialize/deserialize/serialize round trips if supported,
+/// - states of clones,
+/// - legality of all concrete moves and rejection of [`MOVE_NONE`],
+/// - move string round trips if supported,
+/// - availability of results at the end of the game.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{game::GameMethodsRef, testing::GameTester};
+/// fn check_plugin(methods: GameMethodsRef) {
+///     if let Err(failure) = GameTester::new(methods).plies(200).run() {
+///         panic!("{failure}");
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct GameTester<'l> {
+    methods: GameMethodsRef<'l>,
+    init: OwnedGameInit,
+    cycles: u32,
+    plies: u32,
+    seed: Seed,
+}
+
+impl<'l> GameTester<'l> {
+    pub fn new(methods: GameMethodsRef<'l>) -> Self {
+        Self {
+            methods,
+            init: OwnedGameInit::Default,
+            cycles: 8,
+            plies: 100,
+            seed: Seed::new(1),
+        }
+    }
+
+    /// Create the games with `init` instead of the default.
+    #[inline]
+    pub fn init(mut self, init: OwnedGameInit) -> Self {
+        self.init = init;
+        self
+    }
+
+    /// Number of create/destroy cycles before the game is played.
+    #[inline]
+    pub fn cycles(mut self, cycles: u32) -> Self {
+        self.cycles = cycles;
+        self
+    }
+
+    /// Maximum number of moves to play.
+    #[inline]
+    pub fn plies(mut self, plies: u32) -> Self {
+        self.plies = plies;
+        self
+    }
+
+    /// Seed for choosing the moves.
+    #[inline]
+    pub fn seed(mut self, seed: Seed) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Run all checks and return the first failure.
+    pub fn run(&self) -> Result<(), TestFailure> {
+        for _ in 0..self.cycles {
+            self.create()
+                .map_err(|e| TestFailure::new("create", 0, e))?;
+        }
+
+        let game = self
+            .create()
+            .map_err(|e| TestFailure::new("create", 0, e))?;
+        let mut rng = self.seed.rng();
+        self.play(game, |count| Some(rng.below(count)))
+    }
+
+    /// Run the checks on a game created from fuzzer `input`.
+    ///
+    /// Moves are chosen by [`FuzzInput::choices`] and the settings of this
+    /// tester except for the init info are used.
+    /// Inits which are rejected by the game are no failure.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use arbitrary::{Arbitrary, Unstructured};
+    /// # use mirabel_sys::{game::GameMethodsRef, testing::*};
+    /// fn fuzz_target(methods: GameMethodsRef, data: &[u8]) {
+    ///     let Ok(input) = FuzzInput::arbitrary(&mut Unstructured::new(data)) else {
+    ///         return;
+    ///     };
+    ///     if let Err(failure) = GameTester::new(methods).fuzz(&input) {
+    ///         panic!("{failure}");
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "arbitrary")]
+    pub fn fuzz(&self, input: &FuzzInput) -> Result<(), TestFailure> {
+        let tester = self.clone().init(input.init.clone());
+        let Ok(game) = tester.create() else {
+            return Ok(());
+        };
+        let mut choices = input.choices.iter();
+        tester.play(game, |count| choices.next().map(|c| c % count))
+    }
+
+    /// Check and play `game` with moves picked by `choose` until it returns
+    /// [`None`].
+    ///
+    /// `choose` receives the number of moves to choose from.
+    fn play(
+        &self,
+        mut game: Game,
+        mut choose: impl FnMut(u32) -> Option<u32>,
+    ) -> Result<(), TestFailure> {
+        for ply in 0..self.plies {
+            let moves = self.check_state(&mut game, ply)?;
+            if moves.is_empty() {
+                game.results()
+                    .map_err(|e| TestFailure::new("results", ply, e))?;
+                return Ok(());
+            }
+
+            let count = moves.len().try_into().expect("too many moves");
+            let Some(index) = choose(count) else {
+                return Ok(());
+            };
+            let (player, code) = moves[index as usize];
+            game.make_move(player, code)
+                .map_err(|e| TestFailure::new("make_move", ply, e))?;
+        }
+        Ok(())
+    }
+
+    /// Check the current state and return all moves.
+    fn check_state(
+        &self,
+        game: &mut Game,
+        ply: u32,
+    ) -> Result<Vec<(PlayerId, move_code)>, TestFailure> {
+        let fail = |check| move |e| TestFailure::new(check, ply, e);
+
+        let mut state = String::new();
+        game.export_state(&mut state)
+            .map_err(fail("export_state"))?;
+
+        let mut imported = self.create().map_err(fail("create"))?;
+        imported
+            .import_state(Some(&state))
+            .map_err(fail("import_state"))?;
+        expect_state(&mut imported, &state).map_err(fail("import_state round trip"))?;
+
+        let mut clone = game.try_clone().map_err(fail("clone"))?;
+        expect_state(&mut clone, &state).map_err(fail("clone state"))?;
+
+        match game.serialize() {
+            Err(e) if e.code == ErrorCode::FeatureUnsupported => {}
+            serialized => {
+                let serialized = serialized.map_err(fail("serialize"))?;
+                let mut deserialized = self
+                    .create_from(GameInit::Serialized(serialized.clone()))
+                    .map_err(fail("create from serialized"))?;
+                expect_state(&mut deserialized, &state).map_err(fail("serialize round trip"))?;
+                let reserialized = deserialized.serialize().map_err(fail("serialize"))?;
+                if reserialized != serialized {
+                    return Err(fail("serialize round trip")(mismatch(
+                        "serialized data changed after deserializing",
+                    )));
+                }
+            }
+        }
+
+        let mut moves = vec![];
+        let mut string = String::new();
+        for player in game.players_to_move().map_err(fail("players_to_move"))? {
+            if game.check_legal_move(player, MOVE_NONE).is_ok() {
+                return Err(fail("is_legal_move")(mismatch("MOVE_NONE is legal")));
+            }
+
+            for code in game
+                .concrete_moves(player)
+                .map_err(fail("get_concrete_moves"))?
+            {
+                game.check_legal_move(player, code)
+                    .map_err(fail("is_legal_move"))?;
+
+                match game.move_to_str(player, code, &mut string) {
+                    Err(e) if e.code == ErrorCode::FeatureUnsupported => {}
+                    result => {
+                        result.map_err(fail("get_move_str"))?;
+                        let parsed = game
+                            .str_to_move(player, &string)
+                            .map_err(fail("get_move_code"))?;
+                        if parsed != code {
+                            return Err(fail("move string round trip")(mismatch(format!(
+                                "{string:?} parsed as {parsed} instead of {code}"
+                            ))));
+                        }
+                    }
+                }
+
+                moves.push((player, code));
+            }
+        }
+        Ok(moves)
+    }
+
+    /// Create a game which must not leave the tester.
+    #[inline]
+    fn create(&self) -> crate::error::Result<Game> {
+        self.create_from(self.init.as_game_init())
+    }
+
+    /// Same as [`GameTester::create`] but with `init` instead of the
+    /// configured init info.
+    fn create_from(&self, init: GameInit) -> crate::error::Result<Game> {
+        // The games are dropped before `run` or `fuzz` return, so within `'l`.
+        unsafe { self.methods.create(init) }
+    }
+}
+
+/// Fuzzer input for [`GameTester::fuzz`].
+///
+/// Consists of random init info, e.g., option strings, and the choices for
+/// picking the moves.
+#[cfg(feature = "arbitrary")]
+#[derive(Debug, Clone)]
+pub struct FuzzInput {
+    pub init: OwnedGameInit,
+    /// Index of the next move, modulo the number of available moves.
+    pub choices: Vec<u32>,
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for FuzzInput {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            init: u.arbitrary()?,
+            choices: u.arbitrary()?,
+        })
+    }
+}
+
+/// A failed check of [`GameTester`].
+#[derive(Debug)]
+pub struct TestFailure {
+    /// Name of the check or game method which failed.
+    pub check: &'static str,
+    /// Number of moves played before the failure.
+    pub ply: u32,
+    pub error: Error,
+}
+
+impl TestFailure {
+    fn new(check: &'static str, ply: u32, error: Error) -> Self {
+        Self { check, ply, error }
+    }
+}
+
+impl Display for TestFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} failed after {} moves: {}",
+            self.check,
+            self.ply,
+            ErrorDisplay(&self.error)
+        )
+    }
+}
+
+impl std::error::Error for TestFailure {}
+
+/// Panic unless `game` is reproduced by importing its exported state into a
+/// fresh game.
+///
+/// The fresh game is created with `init`, usually the init info of `game`.
+/// Both games are compared by their state strings and their IDs if the game
+/// supports them.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{game::Game, game_init::GameInit, player::PlayerId, testing::*};
+/// fn test_opening(game: &mut Game) {
+///     assert_state_roundtrip(game, GameInit::Default);
+///     game.make_move(PlayerId::new(1), 12).unwrap();
+///     assert_state_roundtrip(game, GameInit::Default);
+/// }
+/// ```
+#[track_caller]
+pub fn assert_state_roundtrip(game: &mut Game, init: GameInit) {
+    let mut state = String::new();
+    expect("export_state", game.export_state(&mut state));
+
+    // Dropped before `game`, whose methods are valid for its lifetime.
+    let mut fresh = expect("create", unsafe { game.methods_ref().create(init) });
+    expect("import_state", fresh.import_state(Some(&state)));
+    assert_same_game(game, &mut fresh, &state);
+}
+
+/// Panic unless `game` is reproduced by creating a fresh game from its
+/// [`Game::serialize`] output.
+///
+/// The games are compared like in [`assert_state_roundtrip`].
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{game::Game, player::PlayerId, testing::*};
+/// fn test_opening(game: &mut Game) {
+///     assert_serialize_roundtrip(game);
+///     game.make_move(PlayerId::new(1), 12).unwrap();
+///     assert_serialize_roundtrip(game);
+/// }
+/// ```
+#[track_caller]
+pub fn assert_serialize_roundtrip(game: &mut Game) {
+    let mut state = String::new();
+    expect("export_state", game.export_state(&mut state));
+
+    let init = GameInit::Serialized(expect("serialize", game.serialize()));
+    // Dropped before `game`, whose methods are valid for its lifetime.
+    let mut fresh = expect("create from serialized", unsafe {
+        game.methods_ref().create(init)
+    });
+    assert_same_game(game, &mut fresh, &state);
+}
+
+#[track_caller]
+fn assert_same_game(game: &mut Game, fresh: &mut Game, state: &str) {
+    let mut fresh_state = String::new();
+    expect("export_state", fresh.export_state(&mut fresh_state));
+    assert_eq!(state, fresh_state, "state changed in round trip");
+
+    match (game.id(), fresh.id()) {
+        (Err(e), _) | (_, Err(e)) if e.code == ErrorCode::FeatureUnsupported => {}
+        (id, fresh_id) => assert_eq!(
+            expect("id", id),
+            expect("id", fresh_id),
+            "ID changed in round trip of state {state:?}"
+        ),
+    }
+}
+
+#[track_caller]
+fn expect<T>(method: &str, result: crate::error::Result<T>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(e) => panic!("{method} failed: {}", ErrorDisplay(&e)),
+    }
+}
+
+/// Error message or the [`ErrorCode`] description if there is none.
+struct ErrorDisplay<'l>(&'l Error);
+
+impl<'l> Display for ErrorDisplay<'l> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0.message {
+            ErrorString::None => write!(f, "{}", self.0.code),
+            ErrorString::Static(message) => write!(f, "{message}"),
+            ErrorString::Dynamic(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+fn expect_state(game: &mut Game, expected: &str) -> crate::error::Result<()> {
+    let mut state = String::new();
+    game.export_state(&mut state)?;
+    if state == expected {
+        Ok(())
+    } else {
+        Err(mismatch(format!(
+            "expected state {expected:?} but got {state:?}"
+        )))
+    }
+}
+
+fn mismatch(message: impl Into<String>) -> Error {
+    Error::new_dynamic(ErrorCode::StateCorrupted, message.into().replace('\0', ""))
+}
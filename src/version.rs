@@ -0,0 +1,206 @@
+//! Helpers for plugin and API versions.
+
+use std::{cmp::Ordering, fmt::Display, hash::Hash};
+
+use crate::sys::{self, semver};
+
+/// Version of the _surena_ game API the bindings were generated for.
+///
+/// The API uses a single number which is increased for every breaking change.
+pub const SURENA_GAME_API_VERSION: u64 = sys::SURENA_GAME_API_VERSION;
+
+/// Version of the _mirabel_ frontend API the bindings were generated for.
+#[cfg(feature = "mirabel")]
+pub const MIRABEL_FRONTEND_API_VERSION: u64 = sys::MIRABEL_FRONTEND_API_VERSION;
+
+/// [`SURENA_GAME_API_VERSION`] as the major part of a [`SemVer`].
+///
+/// Every breaking change of the API increases the major version, so
+/// [`SemVer::is_compatible`] works as expected.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::version::*;
+/// assert_eq!(SURENA_GAME_API_VERSION, SURENA_GAME_API_SEMVER.major().into());
+/// assert!(SURENA_GAME_API_SEMVER.is_compatible(SURENA_GAME_API_SEMVER));
+/// ```
+pub const SURENA_GAME_API_SEMVER: SemVer = SemVer::from_api_version(SURENA_GAME_API_VERSION);
+
+/// [`MIRABEL_FRONTEND_API_VERSION`] as the major part of a [`SemVer`].
+#[cfg(feature = "mirabel")]
+pub const MIRABEL_FRONTEND_API_SEMVER: SemVer =
+    SemVer::from_api_version(MIRABEL_FRONTEND_API_VERSION);
+
+/// Fail compilation if the bound API versions differ from the expected ones.
+///
+/// Accepts a comma-separated list of `surena = VERSION` and
+/// `mirabel = VERSION`.
+/// This catches ABI mismatches after updating the bindings before a plugin
+/// crashes at runtime.
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::{assert_api_version, version::SURENA_GAME_API_VERSION};
+/// assert_api_version!(surena = SURENA_GAME_API_VERSION);
+/// ```
+///
+/// ```compile_fail
+/// # use mirabel_sys::assert_api_version;
+/// assert_api_version!(surena = 0);
+/// ```
+#[macro_export]
+macro_rules! assert_api_version {
+    () => {};
+    (surena = $version: expr $(, $($rest: tt)*)?) => {
+        const _: () = ::std::assert!(
+            $crate::version::SURENA_GAME_API_VERSION == $version,
+            "unexpected surena game API version"
+        );
+        $($crate::assert_api_version!($($rest)*);)?
+    };
+    (mirabel = $version: expr $(, $($rest: tt)*)?) => {
+        const _: () = ::std::assert!(
+            $crate::version::MIRABEL_FRONTEND_API_VERSION == $version,
+            "unexpected mirabel frontend API version"
+        );
+        $($crate::assert_api_version!($($rest)*);)?
+    };
+}
+
+/// Semantic version wrapping [`semver`].
+///
+/// # Example
+/// ```
+/// # use mirabel_sys::version::SemVer;
+/// const VERSION: SemVer = SemVer::new(1, 2, 3);
+/// assert_eq!("1.2.3", VERSION.to_string());
+/// assert!(VERSION < SemVer::new(1, 10, 0));
+/// assert!(VERSION.is_compatible(SemVer::new(1, 1, 0)));
+/// assert!(!VERSION.is_compatible(SemVer::new(2, 0, 0)));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct SemVer(semver);
+
+impl SemVer {
+    #[inline]
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self(semver {
+            major,
+            minor,
+            patch,
+        })
+    }
+
+    /// Convert a single-number API version into the major version.
+    ///
+    /// # Panics
+    /// Panics if `version` does not fit into a [`u32`].
+    pub const fn from_api_version(version: u64) -> Self {
+        assert!(version <= u32::MAX as u64, "API version out of range");
+        Self::new(version as u32, 0, 0)
+    }
+
+    #[inline]
+    pub const fn major(self) -> u32 {
+        self.0.major
+    }
+
+    #[inline]
+    pub const fn minor(self) -> u32 {
+        self.0.minor
+    }
+
+    #[inline]
+    pub const fn patch(self) -> u32 {
+        self.0.patch
+    }
+
+    /// Returns `true` if this version can be used where `required` is
+    /// expected.
+    ///
+    /// This follows the semantic versioning rules: The major version must
+    /// match and this version must not be older.
+    /// Before 1.0.0, the minor version must match too.
+    pub const fn is_compatible(self, required: Self) -> bool {
+        let same_series = if required.major() == 0 {
+            self.major() == 0 && self.minor() == required.minor()
+        } else {
+            self.major() == required.major()
+        };
+        same_series && !self.is_older(required)
+    }
+
+    const fn is_older(self, other: Self) -> bool {
+        if self.major() != other.major() {
+            self.major() < other.major()
+        } else if self.minor() != other.minor() {
+            self.minor() < other.minor()
+        } else {
+            self.patch() < other.patch()
+        }
+    }
+
+    #[inline]
+    fn as_tuple(self) -> (u32, u32, u32) {
+        (self.major(), self.minor(), self.patch())
+    }
+}
+
+/// Serialized as `(major, minor, patch)` tuple.
+#[cfg(feature = "serde")]
+impl serde::Serialize for SemVer {
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_tuple().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SemVer {
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (major, minor, patch) = serde::Deserialize::deserialize(deserializer)?;
+        Ok(Self::new(major, minor, patch))
+    }
+}
+
+impl PartialOrd for SemVer {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SemVer {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_tuple().cmp(&other.as_tuple())
+    }
+}
+
+impl Hash for SemVer {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_tuple().hash(state);
+    }
+}
+
+impl Display for SemVer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major(), self.minor(), self.patch())
+    }
+}
+
+impl From<semver> for SemVer {
+    #[inline]
+    fn from(version: semver) -> Self {
+        Self(version)
+    }
+}
+
+impl From<SemVer> for semver {
+    #[inline]
+    fn from(version: SemVer) -> Self {
+        version.0
+    }
+}
@@ -0,0 +1,73 @@
+//! Restricted access to the SDL window of _mirabel_.
+
+use std::{ffi::c_int, marker::PhantomData, ptr::NonNull};
+
+use crate::sys::{
+    SDL_GetDisplayDPI, SDL_GetWindowDisplayIndex, SDL_GetWindowID, SDL_GetWindowSize, SDL_Window,
+};
+
+/// Borrowed handle to the host window.
+///
+/// This only allows queries, so frontends cannot destroy or reconfigure the
+/// window owned by _mirabel_.
+#[derive(Debug, Clone, Copy)]
+pub struct WindowRef<'l> {
+    window: NonNull<SDL_Window>,
+    phantom: PhantomData<&'l SDL_Window>,
+}
+
+impl<'l> WindowRef<'l> {
+    /// Create a new [`WindowRef`] or [`None`] if `window` is NULL.
+    ///
+    /// # Safety
+    /// `window` must be a valid SDL window which outlives `'l`.
+    /// The handle must only be used on the thread which owns the window.
+    #[inline]
+    pub unsafe fn new(window: *mut SDL_Window) -> Option<Self> {
+        NonNull::new(window).map(|window| Self {
+            window,
+            phantom: Default::default(),
+        })
+    }
+
+    #[inline]
+    pub fn id(&self) -> u32 {
+        unsafe { SDL_GetWindowID(self.window.as_ptr()) }
+    }
+
+    /// Size of the window in screen coordinates as `(width, height)`.
+    pub fn size(&self) -> (i32, i32) {
+        let mut width: c_int = 0;
+        let mut height: c_int = 0;
+        unsafe { SDL_GetWindowSize(self.window.as_ptr(), &mut width, &mut height) };
+        (width, height)
+    }
+
+    /// Index of the display containing the center of the window.
+    pub fn display_index(&self) -> Option<i32> {
+        let index = unsafe { SDL_GetWindowDisplayIndex(self.window.as_ptr()) };
+        (index >= 0).then_some(index)
+    }
+
+    /// Diagonal, horizontal and vertical DPI of the window's display.
+    pub fn dpi(&self) -> Option<Dpi> {
+        let index = self.display_index()?;
+        let mut dpi = Dpi::default();
+        let result = unsafe {
+            SDL_GetDisplayDPI(
+                index,
+                &mut dpi.diagonal,
+                &mut dpi.horizontal,
+                &mut dpi.vertical,
+            )
+        };
+        (result == 0).then_some(dpi)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Dpi {
+    pub diagonal: f32,
+    pub horizontal: f32,
+    pub vertical: f32,
+}